@@ -0,0 +1,1182 @@
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use crate::context::Context;
+use crate::device::cl_get_device_info;
+use crate::event::{self, Event};
+use crate::memory::{check_write_flags, MapGuard, MapReadGuard, MappedRegions, Mapping, MemObject, Residency};
+use crate::native::{clCreateBuffer, clCreateSubBuffer, clEnqueueCopyBuffer, clEnqueueCopyBufferRect, clEnqueueFillBuffer, clEnqueueMapBuffer, clEnqueueReadBuffer, clEnqueueReadBufferRect, clEnqueueWriteBuffer, clEnqueueWriteBufferRect, clReleaseMemObject, clRetainMemObject, BufferRegion};
+use crate::queue::CommandQueue;
+use crate::result::{Error, Result};
+use crate::types::{DeviceInfo, EventId, MapFlags, MemFlags, MemId, Pod, BUFFER_CREATE_TYPE_REGION};
+
+/// A strided 2D or 3D view of linear memory used by rectangular transfers, measured in elements.
+///
+/// Element `[x, y, z]` of the view lies at `(origin[2] + z) * slice_pitch + (origin[1] + y) * row_pitch + origin[0] + x`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct RectLayout {
+    origin: [usize; 3],
+    row_pitch: usize,
+    slice_pitch: usize,
+}
+
+impl RectLayout {
+    /// A layout with the given distances between rows and slices, starting at the first element.
+    pub fn new(row_pitch: usize, slice_pitch: usize) -> Self {
+        Self { origin: [0; 3], row_pitch, slice_pitch }
+    }
+
+    /// The layout of a single row-major matrix with the given number of columns and rows.
+    pub fn matrix(columns: usize, rows: usize) -> Self {
+        Self::new(columns, columns * rows)
+    }
+
+    /// Start the view at the given `[x, y, z]` element instead of the first element.
+    pub fn with_origin(mut self, origin: [usize; 3]) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// The `[x, y, z]` element the view starts at.
+    pub fn origin(&self) -> [usize; 3] {
+        self.origin
+    }
+
+    /// The distance between the starts of two rows in elements.
+    pub fn row_pitch(&self) -> usize {
+        self.row_pitch
+    }
+
+    /// The distance between the starts of two slices in elements.
+    pub fn slice_pitch(&self) -> usize {
+        self.slice_pitch
+    }
+
+    /// Make sure the region fits into the pitches and into memory holding `len` elements.
+    fn check(&self, region: [usize; 3], len: usize) -> Result<()> {
+        let [x, y, z] = self.origin;
+        let [width, height, depth] = region;
+        let end = (|| {
+            let row_end = x.checked_add(width)?;
+            let rows_end = y.checked_add(height)?;
+            if region.contains(&0) || row_end > self.row_pitch || rows_end.checked_mul(self.row_pitch)? > self.slice_pitch
+                || !self.slice_pitch.is_multiple_of(self.row_pitch)
+            {
+                return None;
+            }
+            let last_slice = (z.checked_add(depth)? - 1).checked_mul(self.slice_pitch)?;
+            let last_row = (rows_end - 1).checked_mul(self.row_pitch)?;
+            last_slice.checked_add(last_row)?.checked_add(row_end)
+        })();
+        match end {
+            Some(end) if end <= len => Ok(()),
+            _ => Err(Error::InvalidValue),
+        }
+    }
+
+    /// The origin and pitches in bytes for elements of the given size.
+    fn bytes(&self, element_size: usize) -> ([usize; 3], usize, usize) {
+        ([self.origin[0] * element_size, self.origin[1], self.origin[2]], self.row_pitch * element_size, self.slice_pitch * element_size)
+    }
+}
+
+/// A rectangular tile of a row-major matrix.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Tile {
+    /// The column of the first element of the tile.
+    pub column: usize,
+    /// The row of the first element of the tile.
+    pub row: usize,
+    /// The number of columns of the tile.
+    pub columns: usize,
+    /// The number of rows of the tile.
+    pub rows: usize,
+}
+
+impl Tile {
+    /// A tile of `columns` x `rows` elements starting at the given column and row.
+    pub fn new(column: usize, row: usize, columns: usize, rows: usize) -> Self {
+        Self { column, row, columns, rows }
+    }
+}
+
+/// A typed OpenCL buffer holding `len` elements of type `T`.
+///
+/// The buffer is retained when cloned and released when dropped.
+pub struct Buffer<T: Pod> {
+    id: MemId,
+    context: Context,
+    flags: MemFlags,
+    len: usize,
+    residency: Residency,
+    mappings: MappedRegions,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod> Buffer<T> {
+    /// Create an uninitialized buffer with room for `len` elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the buffer is created in.
+    /// * `flags` - How the buffer is accessed by kernels and the host.
+    /// * `len` - The number of elements of the buffer.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain `USE_HOST_PTR` or `COPY_HOST_PTR`, which require host data.
+    /// * `Error::InvalidBufferSize` - The buffer would be empty or larger than the devices allow.
+    /// * `Error::MemObjectAllocationFailure` - The memory for the buffer could not be allocated.
+    pub fn new(context: &Context, flags: MemFlags, len: usize) -> Result<Self> {
+        if flags.contains(MemFlags::USE_HOST_PTR) || flags.contains(MemFlags::COPY_HOST_PTR) {
+            return Err(Error::InvalidValue);
+        }
+        unsafe { Self::create(context, flags, len, std::ptr::null_mut()) }
+    }
+
+    /// Create a buffer initialized with a copy of the given data.
+    ///
+    /// `COPY_HOST_PTR` is added to the given flags.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain `USE_HOST_PTR`.
+    /// * `Error::InvalidBufferSize` - The data is empty or larger than the devices allow.
+    /// * `Error::MemObjectAllocationFailure` - The memory for the buffer could not be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    ///
+    /// let buffer = Buffer::from_slice(&context, MemFlags::READ_WRITE, &[1.0f32, 2.0, 3.0, 4.0])?;
+    /// let mut result = [0.0f32; 4];
+    /// buffer.read(&queue, 0, &mut result, &[])?;
+    /// assert_eq!(result, [1.0, 2.0, 3.0, 4.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_slice(context: &Context, flags: MemFlags, data: &[T]) -> Result<Self> {
+        if flags.contains(MemFlags::USE_HOST_PTR) {
+            return Err(Error::InvalidValue);
+        }
+        unsafe { Self::create(context, flags | MemFlags::COPY_HOST_PTR, data.len(), data.as_ptr() as *mut c_void) }
+    }
+
+    /// Create a buffer using the given host memory as its storage.
+    ///
+    /// `USE_HOST_PTR` is added to the given flags.
+    ///
+    /// # Safety
+    ///
+    /// The memory referenced by `host_ptr` must hold `len` elements and must stay valid
+    /// for as long as the buffer (or any clone of it) is alive.
+    pub unsafe fn from_host_ptr(context: &Context, flags: MemFlags, host_ptr: *mut T, len: usize) -> Result<Self> {
+        if host_ptr.is_null() {
+            return Err(Error::InvalidHostPtr);
+        }
+        Self::create(context, flags | MemFlags::USE_HOST_PTR, len, host_ptr as *mut c_void)
+    }
+
+    unsafe fn create(context: &Context, flags: MemFlags, len: usize, host_ptr: *mut c_void) -> Result<Self> {
+        let size = len.checked_mul(std::mem::size_of::<T>()).ok_or(Error::InvalidBufferSize)?;
+        if size == 0 {
+            return Err(Error::InvalidBufferSize);
+        }
+
+        let mut result: i32 = 0;
+        let id = clCreateBuffer(context.id(), flags.raw(), size, host_ptr, &mut result);
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone(), flags, len, residency: Residency::default(), mappings: MappedRegions::default(), _marker: PhantomData })
+    }
+
+    /// The native handle of the buffer.
+    pub fn id(&self) -> MemId {
+        self.id
+    }
+
+    pub(crate) fn id_ref(&self) -> &MemId {
+        &self.id
+    }
+
+    /// The context the buffer was created in.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// The flags the buffer was created with.
+    pub fn flags(&self) -> MemFlags {
+        self.flags
+    }
+
+    /// The number of elements of the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer has no elements.
+    ///
+    /// This is always `false`, as OpenCL does not allow empty buffers.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The size of the buffer in bytes.
+    pub fn size(&self) -> usize {
+        self.len * std::mem::size_of::<T>()
+    }
+
+    /// Create a sub-buffer covering the given range of elements, which borrows this buffer.
+    ///
+    /// The sub-buffer shares the memory of this buffer and inherits its flags.
+    /// It can be read, written, mapped and passed as a kernel argument like a buffer, e.g. to hand out
+    /// regions of one large allocation to different kernels.
+    ///
+    /// The start of the range must be aligned to `DeviceInfo::MemBaseAddrAlign` of all devices of the context,
+    /// so that the sub-buffer can be used on any of them.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The range is empty or out of bounds.
+    /// * `Error::MisalignedSubBufferOffset` - The start of the range is not aligned for a device of the context.
+    /// * `Error::InvalidMemObject` - This buffer is a sub-buffer itself.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::kernel::Kernel;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::{DeviceType, MemFlags};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let program = Program::from_source(&context, &["__kernel void scale(__global float *a, float b) {}"])?;
+    /// program.build(&[], "")?;
+    ///
+    /// let buffer = Buffer::<f32>::new(&context, MemFlags::READ_WRITE, 1 << 20)?;
+    /// let (first, second) = (buffer.slice(..1 << 19)?, buffer.slice(1 << 19..)?);
+    /// let mut kernel = Kernel::new(&program, "scale")?;
+    /// kernel.set_arg(0, &first)?;
+    /// let mut kernel = Kernel::new(&program, "scale")?;
+    /// kernel.set_arg(0, &second)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Result<SubBuffer<'_, T>> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).ok_or(Error::InvalidValue)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).ok_or(Error::InvalidValue)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        let len = end.checked_sub(start).ok_or(Error::InvalidValue)?;
+        self.check_region(start, len)?;
+
+        let element_size = std::mem::size_of::<T>();
+        let region = BufferRegion { origin: start * element_size, size: len * element_size };
+        for &device in self.context.devices() {
+            let align: u32 = unsafe { cl_get_device_info(device, DeviceInfo::MemBaseAddrAlign) }?;
+            check_alignment(region.origin, align)?;
+        }
+
+        let mut result: i32 = 0;
+        let id = unsafe {
+            clCreateSubBuffer(
+                self.id, MemFlags::NONE.raw(), BUFFER_CREATE_TYPE_REGION, &region as *const BufferRegion as *const c_void, &mut result,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        let buffer = Self {
+            id, context: self.context.clone(), flags: self.flags, len,
+            residency: self.residency.clone(), mappings: self.mappings.clone(), _marker: PhantomData,
+        };
+        Ok(SubBuffer { buffer, offset: start, _parent: PhantomData })
+    }
+
+    fn check_range(&self, offset: usize, len: usize) -> Result<()> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.len => Ok(()),
+            _ => Err(Error::InvalidValue),
+        }
+    }
+
+    fn check_region(&self, offset: usize, len: usize) -> Result<()> {
+        if len == 0 {
+            return Err(Error::InvalidValue);
+        }
+        self.check_range(offset, len)
+    }
+
+    /// Read elements starting at `offset` from the buffer into `data`, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the read on.
+    /// * `offset` - The first element to read.
+    /// * `data` - Where to store the elements read.
+    /// * `wait_list` - Events which must complete before the read starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region being read is out of bounds.
+    /// * `Error::InvalidContext` - The queue and the buffer belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn read(&self, queue: &CommandQueue, offset: usize, data: &mut [T], wait_list: &[Event]) -> Result<Event> {
+        unsafe { self.enqueue_read(queue, true, offset, data, wait_list) }
+    }
+
+    /// Enqueue a read of elements starting at `offset` from the buffer into `data` without waiting for it.
+    ///
+    /// # Safety
+    ///
+    /// `data` must neither be accessed nor freed until the returned event has completed.
+    pub unsafe fn read_async(&self, queue: &CommandQueue, offset: usize, data: &mut [T], wait_list: &[Event]) -> Result<Event> {
+        self.enqueue_read(queue, false, offset, data, wait_list)
+    }
+
+    unsafe fn enqueue_read(&self, queue: &CommandQueue, blocking: bool, offset: usize, data: &mut [T], wait_list: &[Event]) -> Result<Event> {
+        self.check_range(offset, data.len())?;
+        if data.is_empty() {
+            return queue.enqueue_marker(wait_list);
+        }
+
+        let element_size = std::mem::size_of::<T>();
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = clEnqueueReadBuffer(
+            queue.id(), self.id, blocking as u32, offset * element_size, std::mem::size_of_val(data),
+            data.as_mut_ptr() as *mut c_void, num_events, events, &mut event,
+        );
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+
+    /// Write `data` into the buffer starting at `offset`, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the write on.
+    /// * `offset` - The first element to write.
+    /// * `data` - The elements to write.
+    /// * `wait_list` - Events which must complete before the write starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region being written is out of bounds.
+    /// * `Error::InvalidContext` - The queue and the buffer belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn write(&self, queue: &CommandQueue, offset: usize, data: &[T], wait_list: &[Event]) -> Result<Event> {
+        unsafe { self.enqueue_write(queue, true, offset, data, wait_list) }
+    }
+
+    /// Enqueue a write of `data` into the buffer starting at `offset` without waiting for it.
+    ///
+    /// # Safety
+    ///
+    /// `data` must neither be modified nor freed until the returned event has completed.
+    pub unsafe fn write_async(&self, queue: &CommandQueue, offset: usize, data: &[T], wait_list: &[Event]) -> Result<Event> {
+        self.enqueue_write(queue, false, offset, data, wait_list)
+    }
+
+    unsafe fn enqueue_write(&self, queue: &CommandQueue, blocking: bool, offset: usize, data: &[T], wait_list: &[Event]) -> Result<Event> {
+        self.check_range(offset, data.len())?;
+        if data.is_empty() {
+            return queue.enqueue_marker(wait_list);
+        }
+
+        let element_size = std::mem::size_of::<T>();
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = clEnqueueWriteBuffer(
+            queue.id(), self.id, blocking as u32, offset * element_size, std::mem::size_of_val(data),
+            data.as_ptr() as *const c_void, num_events, events, &mut event,
+        );
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+
+    /// Enqueue a copy of `len` elements from this buffer to `dst`.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the copy on.
+    /// * `dst` - The destination buffer. May be this buffer if the regions do not overlap.
+    /// * `src_offset` - The first element to copy from this buffer.
+    /// * `dst_offset` - The first element to copy to in `dst`.
+    /// * `len` - The number of elements to copy.
+    /// * `wait_list` - Events which must complete before the copy starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - One of the regions is out of bounds.
+    /// * `Error::MemCopyOverlap` - Source and destination are the same buffer and the regions overlap.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn copy_to(&self, queue: &CommandQueue, dst: &Buffer<T>, src_offset: usize, dst_offset: usize, len: usize, wait_list: &[Event]) -> Result<Event> {
+        self.check_range(src_offset, len)?;
+        dst.check_range(dst_offset, len)?;
+        if len == 0 {
+            return queue.enqueue_marker(wait_list);
+        }
+
+        let element_size = std::mem::size_of::<T>();
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueCopyBuffer(
+                queue.id(), self.id, dst.id, src_offset * element_size, dst_offset * element_size, len * element_size,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        dst.residency.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+}
+
+impl<T: Pod> Buffer<T> {
+    /// Enqueue filling `len` elements starting at `offset` with `value`, without a transfer from the host.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the fill on.
+    /// * `value` - The pattern to fill with.
+    /// * `offset` - The first element to fill.
+    /// * `len` - The number of elements to fill.
+    /// * `wait_list` - Events which must complete before the fill starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty or out of bounds,
+    ///   or the size of `T` is not a power of two of at most 128 bytes.
+    /// * `Error::InvalidContext` - The queue and the buffer belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    /// let buffer = Buffer::<[f32; 4]>::new(&context, MemFlags::READ_WRITE, 1 << 20)?;
+    /// buffer.fill(&queue, [0.0, 0.0, 0.0, 1.0], 0, buffer.len(), &[])?.wait()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fill(&self, queue: &CommandQueue, value: T, offset: usize, len: usize, wait_list: &[Event]) -> Result<Event> {
+        let pattern_size = std::mem::size_of::<T>();
+        if !pattern_size.is_power_of_two() || pattern_size > 128 {
+            return Err(Error::InvalidValue);
+        }
+        self.check_region(offset, len)?;
+
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueFillBuffer(
+                queue.id(), self.id, &value as *const T as *const c_void, pattern_size, offset * pattern_size, len * pattern_size,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+
+    /// Map `len` elements starting at `offset` into host memory for writing, blocking until mapped.
+    ///
+    /// On devices sharing memory with the host this usually avoids copying the data.
+    /// The region is unmapped when the returned guard is dropped. Until then, no overlapping region
+    /// can be mapped through clones of the buffer or its sub-buffers.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the map and unmap on.
+    /// * `flags` - Whether the host writes (`WRITE`), also reads (`READ | WRITE`) or completely overwrites
+    ///   (`WRITE_INVALIDATE_REGION`) the region.
+    /// * `offset` - The first element to map.
+    /// * `len` - The number of elements to map.
+    /// * `wait_list` - Events which must complete before the region is mapped.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty or out of bounds, or the flags are invalid or do not request write access.
+    /// * `Error::InvalidOperation` - The flags conflict with the host access flags of the buffer,
+    ///   or an overlapping region is mapped through another handle.
+    /// * `Error::MapFailure` - The region could not be mapped.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, MapFlags, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::CPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    /// let mut buffer = Buffer::<f32>::new(&context, MemFlags::ALLOC_HOST_PTR, 1024)?;
+    /// let mut mapped = buffer.map(&queue, MapFlags::WRITE_INVALIDATE_REGION, 0, 1024, &[])?;
+    /// mapped.fill(1.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn map<'a>(&'a mut self, queue: &'a CommandQueue, flags: MapFlags, offset: usize, len: usize, wait_list: &[Event]) -> Result<MapGuard<'a, T>> {
+        check_write_flags(flags)?;
+        Ok(MapGuard::new(self.enqueue_map(queue, flags, 0, offset, len, wait_list)?))
+    }
+
+    /// Map `len` elements starting at `offset` into host memory for reading, blocking until mapped.
+    ///
+    /// The region is unmapped when the returned guard is dropped, see [Buffer::map]. Until then,
+    /// overlapping regions can only be mapped for reading through clones of the buffer or its sub-buffers.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty or out of bounds.
+    /// * `Error::InvalidOperation` - The buffer was created with `MemFlags::HOST_WRITE_ONLY` or `HOST_NO_ACCESS`,
+    ///   or an overlapping region is mapped for writing through another handle.
+    /// * `Error::MapFailure` - The region could not be mapped.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn map_read<'a>(&'a mut self, queue: &'a CommandQueue, offset: usize, len: usize, wait_list: &[Event]) -> Result<MapReadGuard<'a, T>> {
+        Ok(MapReadGuard::new(self.enqueue_map(queue, MapFlags::READ, 0, offset, len, wait_list)?))
+    }
+
+    /// Map a region of the buffer, which starts at element `origin` of its parent if it is a sub-buffer.
+    fn enqueue_map<'a>(
+        &self, queue: &'a CommandQueue, flags: MapFlags, origin: usize, offset: usize, len: usize, wait_list: &[Event],
+    ) -> Result<Mapping<'a, T>> {
+        self.check_region(offset, len)?;
+
+        let element_size = std::mem::size_of::<T>();
+        let writable = check_write_flags(flags).is_ok();
+        let lock = self.mappings.lock([(origin + offset) * element_size, 0, 0], [len * element_size, 1, 1], writable)?;
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut result: i32 = 0;
+        let ptr = unsafe {
+            clEnqueueMapBuffer(
+                queue.id(), self.id, 1, flags.raw(), offset * element_size, len * element_size,
+                num_events, events, std::ptr::null_mut(), &mut result,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        Ok(unsafe { Mapping::new(self.id, queue, ptr as *mut T, len, 0, 0, lock) })
+    }
+}
+
+impl<T: Pod> Buffer<T> {
+    /// Read a rectangular region of the buffer into a rectangular region of `data`, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the read on.
+    /// * `buffer_layout` - The layout of the region in the buffer.
+    /// * `host_layout` - The layout of the region in `data`.
+    /// * `region` - The `[width, height, depth]` of the region in elements, rows and slices.
+    /// * `data` - Where to store the elements read.
+    /// * `wait_list` - Events which must complete before the read starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty, does not fit into the pitches of a layout or is out of bounds.
+    /// * `Error::InvalidContext` - The queue and the buffer belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn read_rect(
+        &self, queue: &CommandQueue, buffer_layout: RectLayout, host_layout: RectLayout, region: [usize; 3], data: &mut [T], wait_list: &[Event],
+    ) -> Result<Event> {
+        buffer_layout.check(region, self.len)?;
+        host_layout.check(region, data.len())?;
+
+        let element_size = std::mem::size_of::<T>();
+        let (buffer_origin, buffer_row_pitch, buffer_slice_pitch) = buffer_layout.bytes(element_size);
+        let (host_origin, host_row_pitch, host_slice_pitch) = host_layout.bytes(element_size);
+        let region = [region[0] * element_size, region[1], region[2]];
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueReadBufferRect(
+                queue.id(), self.id, 1, buffer_origin.as_ptr(), host_origin.as_ptr(), region.as_ptr(),
+                buffer_row_pitch, buffer_slice_pitch, host_row_pitch, host_slice_pitch, data.as_mut_ptr() as *mut c_void,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+
+    /// Write a rectangular region of `data` into a rectangular region of the buffer, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the write on.
+    /// * `buffer_layout` - The layout of the region in the buffer.
+    /// * `host_layout` - The layout of the region in `data`.
+    /// * `region` - The `[width, height, depth]` of the region in elements, rows and slices.
+    /// * `data` - The elements to write.
+    /// * `wait_list` - Events which must complete before the write starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty, does not fit into the pitches of a layout or is out of bounds.
+    /// * `Error::InvalidContext` - The queue and the buffer belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn write_rect(
+        &self, queue: &CommandQueue, buffer_layout: RectLayout, host_layout: RectLayout, region: [usize; 3], data: &[T], wait_list: &[Event],
+    ) -> Result<Event> {
+        buffer_layout.check(region, self.len)?;
+        host_layout.check(region, data.len())?;
+
+        let element_size = std::mem::size_of::<T>();
+        let (buffer_origin, buffer_row_pitch, buffer_slice_pitch) = buffer_layout.bytes(element_size);
+        let (host_origin, host_row_pitch, host_slice_pitch) = host_layout.bytes(element_size);
+        let region = [region[0] * element_size, region[1], region[2]];
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueWriteBufferRect(
+                queue.id(), self.id, 1, buffer_origin.as_ptr(), host_origin.as_ptr(), region.as_ptr(),
+                buffer_row_pitch, buffer_slice_pitch, host_row_pitch, host_slice_pitch, data.as_ptr() as *const c_void,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+
+    /// Enqueue a copy of a rectangular region of this buffer to a rectangular region of `dst`.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the copy on.
+    /// * `dst` - The destination buffer. May be this buffer if the regions do not overlap.
+    /// * `src_layout` - The layout of the region in this buffer.
+    /// * `dst_layout` - The layout of the region in `dst`.
+    /// * `region` - The `[width, height, depth]` of the region in elements, rows and slices.
+    /// * `wait_list` - Events which must complete before the copy starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty, does not fit into the pitches of a layout or is out of bounds.
+    /// * `Error::MemCopyOverlap` - Source and destination are the same buffer and the regions overlap.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn copy_rect_to(
+        &self, queue: &CommandQueue, dst: &Buffer<T>, src_layout: RectLayout, dst_layout: RectLayout, region: [usize; 3], wait_list: &[Event],
+    ) -> Result<Event> {
+        src_layout.check(region, self.len)?;
+        dst_layout.check(region, dst.len)?;
+
+        let element_size = std::mem::size_of::<T>();
+        let (src_origin, src_row_pitch, src_slice_pitch) = src_layout.bytes(element_size);
+        let (dst_origin, dst_row_pitch, dst_slice_pitch) = dst_layout.bytes(element_size);
+        let region = [region[0] * element_size, region[1], region[2]];
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueCopyBufferRect(
+                queue.id(), self.id, dst.id, src_origin.as_ptr(), dst_origin.as_ptr(), region.as_ptr(),
+                src_row_pitch, src_slice_pitch, dst_row_pitch, dst_slice_pitch, num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.residency.used_by(queue);
+        dst.residency.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+
+    /// Write a tile of a row-major host matrix into the buffer, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the write on.
+    /// * `buffer_layout` - Where the tile is stored in the buffer.
+    /// * `matrix` - The row-major host matrix.
+    /// * `columns` - The number of columns of the host matrix.
+    /// * `tile` - The tile of the host matrix to transfer.
+    /// * `wait_list` - Events which must complete before the write starts.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as [Buffer::write_rect] may be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::{Buffer, RectLayout, Tile};
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    /// let matrix = vec![0.0f32; 4096 * 4096];
+    ///
+    /// // Copy the 256x256 tile at row 512, column 1024 into a buffer with rows padded to 264 elements.
+    /// let tile = Buffer::<f32>::new(&context, MemFlags::READ_ONLY, 264 * 256)?;
+    /// tile.write_tile(&queue, RectLayout::matrix(264, 256), &matrix, 4096, Tile::new(1024, 512, 256, 256), &[])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_tile(
+        &self, queue: &CommandQueue, buffer_layout: RectLayout, matrix: &[T], columns: usize, tile: Tile, wait_list: &[Event],
+    ) -> Result<Event> {
+        let host_layout = matrix_layout(matrix.len(), columns)?.with_origin([tile.column, tile.row, 0]);
+        self.write_rect(queue, buffer_layout, host_layout, [tile.columns, tile.rows, 1], matrix, wait_list)
+    }
+
+    /// Read a tile of a row-major host matrix from the buffer, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the read on.
+    /// * `buffer_layout` - Where the tile is stored in the buffer.
+    /// * `matrix` - The row-major host matrix the tile is stored into.
+    /// * `columns` - The number of columns of the host matrix.
+    /// * `tile` - The tile of the host matrix to transfer.
+    /// * `wait_list` - Events which must complete before the read starts.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as [Buffer::read_rect] may be returned.
+    pub fn read_tile(
+        &self, queue: &CommandQueue, buffer_layout: RectLayout, matrix: &mut [T], columns: usize, tile: Tile, wait_list: &[Event],
+    ) -> Result<Event> {
+        let host_layout = matrix_layout(matrix.len(), columns)?.with_origin([tile.column, tile.row, 0]);
+        self.read_rect(queue, buffer_layout, host_layout, [tile.columns, tile.rows, 1], matrix, wait_list)
+    }
+}
+
+/// The layout of a row-major matrix of `len` elements with the given number of columns.
+fn matrix_layout(len: usize, columns: usize) -> Result<RectLayout> {
+    if columns == 0 || !len.is_multiple_of(columns) {
+        return Err(Error::InvalidValue);
+    }
+    Ok(RectLayout::matrix(columns, len / columns))
+}
+
+/// Check that the byte offset of a sub-buffer is aligned to `DeviceInfo::MemBaseAddrAlign`, which is given in bits.
+fn check_alignment(origin: usize, align_bits: u32) -> Result<()> {
+    let align = (align_bits as usize / 8).max(1);
+    if !origin.is_multiple_of(align) {
+        return Err(Error::MisalignedSubBufferOffset);
+    }
+    Ok(())
+}
+
+/// A typed region of a parent buffer, created with [Buffer::slice].
+///
+/// The sub-buffer borrows the handle of the parent buffer it was created from,
+/// so that handle can neither be mapped nor dropped while the sub-buffer exists.
+/// Other clones of the parent buffer and other sub-buffers may still access the same memory,
+/// but mappings are tracked across all of them, so no region can be mapped for writing while
+/// an overlapping region is mapped through another handle.
+/// The sub-buffer itself can not be cloned, so it can not outlive the borrow either.
+pub struct SubBuffer<'a, T: Pod> {
+    buffer: Buffer<T>,
+    offset: usize,
+    _parent: PhantomData<&'a Buffer<T>>,
+}
+
+impl<T: Pod> SubBuffer<'_, T> {
+    /// The native handle of the sub-buffer.
+    pub fn id(&self) -> MemId {
+        self.buffer.id
+    }
+
+    /// The context the sub-buffer was created in.
+    pub fn context(&self) -> &Context {
+        &self.buffer.context
+    }
+
+    /// The flags the sub-buffer was created with, which are inherited from the parent buffer.
+    pub fn flags(&self) -> MemFlags {
+        self.buffer.flags
+    }
+
+    /// The offset of the sub-buffer within its parent buffer in elements.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The number of elements of the sub-buffer.
+    pub fn len(&self) -> usize {
+        self.buffer.len
+    }
+
+    /// Whether the sub-buffer has no elements.
+    ///
+    /// This is always `false`, as empty sub-buffers are rejected.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// The size of the sub-buffer in bytes.
+    pub fn size(&self) -> usize {
+        self.buffer.size()
+    }
+
+    pub(crate) fn as_buffer(&self) -> &Buffer<T> {
+        &self.buffer
+    }
+
+    /// Read elements of the sub-buffer starting at `offset` into `data`, blocking until done, see [Buffer::read].
+    pub fn read(&self, queue: &CommandQueue, offset: usize, data: &mut [T], wait_list: &[Event]) -> Result<Event> {
+        self.buffer.read(queue, offset, data, wait_list)
+    }
+
+    /// Enqueue a read of elements of the sub-buffer starting at `offset` into `data`, see [Buffer::read_async].
+    ///
+    /// # Safety
+    ///
+    /// `data` must neither be accessed nor freed until the returned event has completed.
+    pub unsafe fn read_async(&self, queue: &CommandQueue, offset: usize, data: &mut [T], wait_list: &[Event]) -> Result<Event> {
+        self.buffer.read_async(queue, offset, data, wait_list)
+    }
+
+    /// Write `data` into the sub-buffer starting at `offset`, blocking until done, see [Buffer::write].
+    pub fn write(&self, queue: &CommandQueue, offset: usize, data: &[T], wait_list: &[Event]) -> Result<Event> {
+        self.buffer.write(queue, offset, data, wait_list)
+    }
+
+    /// Enqueue a write of `data` into the sub-buffer starting at `offset`, see [Buffer::write_async].
+    ///
+    /// # Safety
+    ///
+    /// `data` must neither be modified nor freed until the returned event has completed.
+    pub unsafe fn write_async(&self, queue: &CommandQueue, offset: usize, data: &[T], wait_list: &[Event]) -> Result<Event> {
+        self.buffer.write_async(queue, offset, data, wait_list)
+    }
+
+    /// Enqueue a copy of `len` elements from the sub-buffer to `dst`, see [Buffer::copy_to].
+    pub fn copy_to(&self, queue: &CommandQueue, dst: &Buffer<T>, src_offset: usize, dst_offset: usize, len: usize, wait_list: &[Event]) -> Result<Event> {
+        self.buffer.copy_to(queue, dst, src_offset, dst_offset, len, wait_list)
+    }
+
+    /// Enqueue filling `len` elements of the sub-buffer starting at `offset` with `value`, see [Buffer::fill].
+    pub fn fill(&self, queue: &CommandQueue, value: T, offset: usize, len: usize, wait_list: &[Event]) -> Result<Event> {
+        self.buffer.fill(queue, value, offset, len, wait_list)
+    }
+
+    /// Map `len` elements of the sub-buffer starting at `offset` into host memory for writing, see [Buffer::map].
+    pub fn map<'a>(&'a mut self, queue: &'a CommandQueue, flags: MapFlags, offset: usize, len: usize, wait_list: &[Event]) -> Result<MapGuard<'a, T>> {
+        check_write_flags(flags)?;
+        Ok(MapGuard::new(self.buffer.enqueue_map(queue, flags, self.offset, offset, len, wait_list)?))
+    }
+
+    /// Map `len` elements of the sub-buffer starting at `offset` into host memory for reading, see [Buffer::map_read].
+    pub fn map_read<'a>(&'a mut self, queue: &'a CommandQueue, offset: usize, len: usize, wait_list: &[Event]) -> Result<MapReadGuard<'a, T>> {
+        Ok(MapReadGuard::new(self.buffer.enqueue_map(queue, MapFlags::READ, self.offset, offset, len, wait_list)?))
+    }
+}
+
+impl<T: Pod> MemObject for SubBuffer<'_, T> {
+    fn mem_id(&self) -> MemId {
+        self.buffer.id
+    }
+
+    fn residency(&self) -> Option<&Residency> {
+        Some(&self.buffer.residency)
+    }
+}
+
+impl<T: Pod> MemObject for Buffer<T> {
+    fn mem_id(&self) -> MemId {
+        self.id
+    }
+
+    fn residency(&self) -> Option<&Residency> {
+        Some(&self.residency)
+    }
+}
+
+impl<T: Pod> Clone for Buffer<T> {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainMemObject(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self {
+            id: self.id, context: self.context.clone(), flags: self.flags, len: self.len,
+            residency: self.residency.clone(), mappings: self.mappings.clone(), _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Pod> Drop for Buffer<T> {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseMemObject(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::cl_get_mem_object_info;
+    use crate::platform::cl_get_platform_ids;
+    use crate::types::{CommandQueueProperties, DeviceType, MemInfo};
+
+    #[test]
+    fn transfer_data() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+            let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE).unwrap();
+
+            let source = Buffer::from_slice(&context, MemFlags::READ_ONLY, &[1u32, 2, 3, 4]).unwrap();
+            assert_eq!(source.len(), 4);
+            assert_eq!(source.mem_size().unwrap(), 16);
+
+            let target = Buffer::<u32>::new(&context, MemFlags::READ_WRITE, 8).unwrap();
+            let written = target.write(&queue, 0, &[0; 8], &[]).unwrap();
+            let copied = source.copy_to(&queue, &target, 1, 4, 3, &[written]).unwrap();
+
+            let mut data = [0u32; 8];
+            target.read(&queue, 0, &mut data, &[copied]).unwrap();
+            assert_eq!(data, [0, 0, 0, 0, 2, 3, 4, 0]);
+
+            let filled = target.fill(&queue, 9, 1, 2, &[]).unwrap();
+            target.read(&queue, 0, &mut data, &[filled]).unwrap();
+            assert_eq!(data, [0, 9, 9, 0, 2, 3, 4, 0]);
+            assert_eq!(target.fill(&queue, 9, 7, 2, &[]).err(), Some(Error::InvalidValue));
+            let vectors = Buffer::<[u32; 3]>::new(&context, MemFlags::READ_WRITE, 2).unwrap();
+            assert_eq!(vectors.fill(&queue, [1, 2, 3], 0, 2, &[]).err(), Some(Error::InvalidValue));
+
+            assert_eq!(target.read(&queue, 6, &mut data, &[]).err(), Some(Error::InvalidValue));
+            target.read(&queue, 0, &mut [], &[]).unwrap();
+            assert_eq!(source.copy_to(&queue, &target, 2, 0, 3, &[]).err(), Some(Error::InvalidValue));
+        }
+    }
+
+    #[test]
+    fn rect_layouts() {
+        let layout = RectLayout::matrix(8, 4);
+        assert!(layout.check([8, 4, 1], 32).is_ok());
+        assert!(layout.check([8, 4, 1], 31).is_err());
+        assert!(layout.with_origin([2, 1, 0]).check([6, 3, 1], 32).is_ok());
+        assert!(layout.with_origin([3, 1, 0]).check([6, 3, 1], 32).is_err());
+        assert!(layout.with_origin([2, 2, 0]).check([6, 3, 1], 32).is_err());
+        assert!(layout.check([8, 4, 3], 96).is_ok());
+        assert!(layout.with_origin([0, 0, 1]).check([8, 4, 3], 96).is_err());
+        assert!(layout.check([0, 4, 1], 32).is_err());
+        assert!(RectLayout::new(8, 36).check([8, 4, 1], 64).is_err());
+        assert_eq!(layout.with_origin([2, 1, 3]).bytes(4), ([8, 1, 3], 32, 128));
+        assert_eq!(matrix_layout(12, 4), Ok(RectLayout::matrix(4, 3)));
+        assert_eq!(matrix_layout(12, 5), Err(Error::InvalidValue));
+    }
+
+    #[test]
+    fn sub_buffer_alignment() {
+        assert_eq!(check_alignment(0, 1024), Ok(()));
+        assert_eq!(check_alignment(256, 1024), Ok(()));
+        assert_eq!(check_alignment(64, 1024), Err(Error::MisalignedSubBufferOffset));
+        assert_eq!(check_alignment(3, 0), Ok(()));
+    }
+
+    #[test]
+    fn slice_buffers() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+            let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE).unwrap();
+
+            let mut align = 2 * std::mem::size_of::<u32>();
+            for &device in context.devices() {
+                align = align.max(unsafe { cl_get_device_info::<u32>(device, DeviceInfo::MemBaseAddrAlign) }.unwrap() as usize / 8);
+            }
+            let step = align / std::mem::size_of::<u32>();
+            let data: Vec<u32> = (0..4 * step as u32).collect();
+            let buffer = Buffer::from_slice(&context, MemFlags::READ_WRITE, &data).unwrap();
+
+            let slice = buffer.slice(step..2 * step).unwrap();
+            assert_eq!(slice.len(), step);
+            assert_eq!(slice.offset(), step);
+            let offset: usize = unsafe { cl_get_mem_object_info(slice.id(), MemInfo::Offset) }.unwrap();
+            assert_eq!(offset, align);
+
+            let mut region = vec![0u32; step];
+            slice.read(&queue, 0, &mut region, &[]).unwrap();
+            assert_eq!(region, data[step..2 * step]);
+            assert_eq!(buffer.slice(3 * step..).unwrap().len(), step);
+
+            assert_eq!(buffer.slice(1..step).err(), Some(Error::MisalignedSubBufferOffset));
+            assert_eq!(buffer.slice(step..step).err(), Some(Error::InvalidValue));
+            assert_eq!(buffer.slice(..=4 * step).err(), Some(Error::InvalidValue));
+
+            let (mut first, mut second) = (buffer.slice(..2 * step).unwrap(), buffer.slice(step..3 * step).unwrap());
+            let mapped = first.map(&queue, MapFlags::WRITE, step, 1, &[]).unwrap();
+            assert_eq!(second.map(&queue, MapFlags::WRITE, 0, 1, &[]).err(), Some(Error::InvalidOperation));
+            assert_eq!(second.map_read(&queue, 0, 1, &[]).err(), Some(Error::InvalidOperation));
+            drop(second.map(&queue, MapFlags::WRITE, 1, step, &[]).unwrap());
+            drop(mapped);
+            drop(second.map(&queue, MapFlags::WRITE, 0, 1, &[]).unwrap());
+        }
+    }
+
+    #[test]
+    fn destructor_callbacks() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let buffer = Buffer::<u32>::new(&context, MemFlags::READ_WRITE, 16).unwrap();
+            let copy = buffer.clone();
+            for index in 0..2 {
+                let sender = sender.clone();
+                buffer.set_destructor_callback(move || sender.send(index).unwrap()).unwrap();
+            }
+
+            drop(buffer);
+            assert!(receiver.try_recv().is_err());
+            drop(copy);
+            let timeout = std::time::Duration::from_secs(5);
+            assert_eq!(receiver.recv_timeout(timeout), Ok(1));
+            assert_eq!(receiver.recv_timeout(timeout), Ok(0));
+        }
+    }
+
+    #[test]
+    fn map_buffers() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+            let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE).unwrap();
+
+            let mut buffer = Buffer::from_slice(&context, MemFlags::READ_WRITE, &[1u32, 2, 3, 4]).unwrap();
+            {
+                let mut mapped = buffer.map(&queue, MapFlags::READ | MapFlags::WRITE, 1, 2, &[]).unwrap();
+                assert_eq!(*mapped, [2, 3]);
+                mapped[1] = 5;
+            }
+            let mapped = buffer.map(&queue, MapFlags::WRITE_INVALIDATE_REGION, 3, 1, &[]).unwrap();
+            let unmapped = mapped.unmap().unwrap();
+
+            let mapped = buffer.map_read(&queue, 0, 3, &[unmapped]).unwrap();
+            assert_eq!(*mapped, [1, 2, 5]);
+            drop(mapped);
+
+            assert_eq!(buffer.map_read(&queue, 3, 2, &[]).err(), Some(Error::InvalidValue));
+            assert_eq!(buffer.map(&queue, MapFlags::READ, 0, 2, &[]).err(), Some(Error::InvalidValue));
+
+            let mut shared = buffer.clone();
+            let mapped = buffer.map(&queue, MapFlags::WRITE, 0, 2, &[]).unwrap();
+            assert_eq!(shared.map(&queue, MapFlags::WRITE, 1, 2, &[]).err(), Some(Error::InvalidOperation));
+            assert_eq!(shared.map_read(&queue, 1, 1, &[]).err(), Some(Error::InvalidOperation));
+            drop(shared.map(&queue, MapFlags::WRITE, 2, 2, &[]).unwrap());
+            drop(mapped);
+            let mapped = shared.map_read(&queue, 0, 4, &[]).unwrap();
+            drop(buffer.map_read(&queue, 1, 2, &[]).unwrap());
+            assert_eq!(buffer.map(&queue, MapFlags::WRITE, 3, 1, &[]).err(), Some(Error::InvalidOperation));
+            mapped.unmap().unwrap().wait().unwrap();
+            drop(buffer.map(&queue, MapFlags::WRITE, 3, 1, &[]).unwrap());
+        }
+    }
+
+    #[test]
+    fn transfer_tiles() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+            let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE).unwrap();
+
+            let matrix: Vec<u32> = (0..36).collect();
+            let tile = Buffer::<u32>::new(&context, MemFlags::READ_WRITE, 8).unwrap();
+            tile.write_tile(&queue, RectLayout::matrix(4, 2), &matrix, 6, Tile::new(1, 2, 3, 2), &[]).unwrap();
+
+            let mut data = [0u32; 8];
+            tile.read(&queue, 0, &mut data, &[]).unwrap();
+            assert_eq!(data[..3], [13, 14, 15]);
+            assert_eq!(data[4..7], [19, 20, 21]);
+
+            let copy = Buffer::<u32>::new(&context, MemFlags::READ_WRITE, 8).unwrap();
+            let copied = tile.copy_rect_to(&queue, &copy, RectLayout::matrix(4, 2), RectLayout::matrix(4, 2), [3, 2, 1], &[]).unwrap();
+            let mut result = vec![0u32; 36];
+            copy.read_tile(&queue, RectLayout::matrix(4, 2), &mut result, 6, Tile::new(3, 4, 3, 2), &[copied]).unwrap();
+            assert_eq!(result[27..30], [13, 14, 15]);
+            assert_eq!(result[33..36], [19, 20, 21]);
+
+            assert_eq!(tile.write_tile(&queue, RectLayout::matrix(4, 2), &matrix, 6, Tile::new(4, 2, 3, 2), &[]).err(), Some(Error::InvalidValue));
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::ffi::{c_char, c_void, CStr};
+use std::sync::Arc;
+use crate::info;
+use crate::native::{clCreateContext, clCreateContextFromType, clGetContextInfo, clReleaseContext, clRetainContext, ContextNotify};
+use crate::result::{Error, Result};
+use crate::types::{ContextId, ContextInfo, ContextProperty, DeviceId, DeviceType, PlatformId, CONTEXT_PLATFORM};
+
+type Notify = Box<dyn Fn(&str, &[u8]) + Send + Sync>;
+
+extern "C" fn context_notify(errinfo: *const c_char, private_info: *const c_void, cb: usize, user_data: *mut c_void) {
+    let notify = unsafe { &*(user_data as *const Notify) };
+    let message = if errinfo.is_null() { "".into() } else { unsafe { CStr::from_ptr(errinfo) }.to_string_lossy() };
+    let private_info = if private_info.is_null() || cb == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(private_info as *const u8, cb) }
+    };
+    // the notification may be raised on a thread of the implementation, where unwinding is undefined
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| notify(&message, private_info)));
+}
+
+/// An OpenCL context.
+///
+/// The context is retained when cloned and released when dropped.
+/// A notification callback is kept alive until the last handle to the context has been released.
+pub struct Context {
+    id: ContextId,
+    platform: PlatformId,
+    devices: Vec<DeviceId>,
+    notify: Option<Arc<Notify>>,
+}
+
+impl Context {
+    /// Create a context for the given devices of the given platform.
+    ///
+    /// # Arguments
+    ///
+    /// * `platform` - The platform the devices belong to.
+    /// * `devices` - The devices which should be part of the context.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidPlatform` - An invalid platform ID was passed.
+    /// * `Error::InvalidValue` - The list of devices was empty.
+    /// * `Error::InvalidDevice` - An invalid device ID was passed.
+    /// * `Error::DeviceNotAvailable` - One of the devices is currently not available.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::device::cl_get_device_ids;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let platform = Platform::default();
+    /// let devices = cl_get_device_ids(platform.id(), DeviceType::GPU)?;
+    /// let context = Context::new(platform.id(), &devices)?;
+    /// println!("Number of devices: {}", context.devices().len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(platform: PlatformId, devices: &[DeviceId]) -> Result<Self> {
+        Self::create(platform, devices, None)
+    }
+
+    /// Create a context for the given devices of the given platform,
+    /// which reports errors occurring asynchronously in the context to `notify`.
+    ///
+    /// The callback receives the error message and implementation specific binary data useful for debugging.
+    /// It may be called from a thread of the OpenCL implementation at any time while the context exists,
+    /// so it must be thread-safe and must not call blocking OpenCL functions. It can forward to a logger, for example.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [Context::new] may be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::device::cl_get_device_ids;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let platform = Platform::default();
+    /// let devices = cl_get_device_ids(platform.id(), DeviceType::GPU)?;
+    /// let context = Context::with_notify(platform.id(), &devices, |message, _| eprintln!("OpenCL error: {}", message))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_notify<F>(platform: PlatformId, devices: &[DeviceId], notify: F) -> Result<Self>
+    where
+        F: Fn(&str, &[u8]) + Send + Sync + 'static,
+    {
+        Self::create(platform, devices, Some(Arc::new(Box::new(notify))))
+    }
+
+    fn create(platform: PlatformId, devices: &[DeviceId], notify: Option<Arc<Notify>>) -> Result<Self> {
+        if devices.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+
+        let properties = Self::properties(platform);
+        let (pfn_notify, user_data) = Self::notify_args(&notify);
+        let mut result: i32 = 0;
+        let id = unsafe {
+            clCreateContext(properties.as_ptr(), devices.len() as u32, devices.as_ptr(), pfn_notify, user_data, &mut result)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, platform, devices: devices.to_vec(), notify })
+    }
+
+    /// Create a context for all devices of the given type on the given platform.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidPlatform` - An invalid platform ID was passed.
+    /// * `Error::InvalidDeviceType` - An invalid device type was passed.
+    /// * `Error::DeviceNotFound` - No devices of the given type are available.
+    /// * `Error::DeviceNotAvailable` - One of the devices is currently not available.
+    pub fn from_type(platform: PlatformId, device_type: DeviceType) -> Result<Self> {
+        Self::create_from_type(platform, device_type, None)
+    }
+
+    /// Create a context for all devices of the given type on the given platform,
+    /// which reports errors occurring asynchronously in the context to `notify`.
+    ///
+    /// See [Context::with_notify] for the requirements on the callback.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [Context::from_type] may be returned.
+    pub fn from_type_with_notify<F>(platform: PlatformId, device_type: DeviceType, notify: F) -> Result<Self>
+    where
+        F: Fn(&str, &[u8]) + Send + Sync + 'static,
+    {
+        Self::create_from_type(platform, device_type, Some(Arc::new(Box::new(notify))))
+    }
+
+    fn create_from_type(platform: PlatformId, device_type: DeviceType, notify: Option<Arc<Notify>>) -> Result<Self> {
+        let properties = Self::properties(platform);
+        let (pfn_notify, user_data) = Self::notify_args(&notify);
+        let mut result: i32 = 0;
+        let id = unsafe {
+            clCreateContextFromType(properties.as_ptr(), device_type.raw(), pfn_notify, user_data, &mut result)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        let mut context = Self { id, platform, devices: vec![], notify };
+        context.devices = unsafe {
+            info::get_vec(|size, value, size_ret| clGetContextInfo(id, ContextInfo::Devices, size, value, size_ret))
+        }?;
+        Ok(context)
+    }
+
+    fn properties(platform: PlatformId) -> [ContextProperty; 3] {
+        [CONTEXT_PLATFORM, platform as ContextProperty, 0]
+    }
+
+    /// The callback and user data passed to OpenCL, pointing to the boxed closure shared by all handles.
+    fn notify_args(notify: &Option<Arc<Notify>>) -> (Option<ContextNotify>, *mut c_void) {
+        match notify {
+            Some(notify) => (Some(context_notify), Arc::as_ptr(notify) as *mut c_void),
+            None => (None, std::ptr::null_mut()),
+        }
+    }
+
+    /// The native handle of the context.
+    pub fn id(&self) -> ContextId {
+        self.id
+    }
+
+    /// The platform the context was created for.
+    pub fn platform(&self) -> PlatformId {
+        self.platform
+    }
+
+    /// The devices which are part of the context.
+    pub fn devices(&self) -> &[DeviceId] {
+        &self.devices
+    }
+
+    /// The current reference count of the context.
+    ///
+    /// # Remarks
+    ///
+    /// The reference count should be considered immediately stale.
+    /// It is only useful for identifying memory leaks.
+    pub fn reference_count(&self) -> Result<u32> {
+        unsafe {
+            info::get_value(|size, value, size_ret| clGetContextInfo(self.id, ContextInfo::ReferenceCount, size, value, size_ret))
+        }
+    }
+}
+
+impl Clone for Context {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainContext(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id: self.id, platform: self.platform, devices: self.devices.clone(), notify: self.notify.clone() }
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        let notify = self.notify.take();
+        if let Some(notify) = &notify {
+            // Events can keep the context alive after its last handle has been dropped.
+            // Leak the callback rather than freeing it while OpenCL may still call it.
+            if Arc::strong_count(notify) == 1 && self.reference_count().map_or(true, |count| count > 1) {
+                std::mem::forget(notify.clone());
+            }
+        }
+
+        let result = unsafe { clReleaseContext(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::cl_get_device_ids;
+    use crate::platform::cl_get_platform_ids;
+
+    #[test]
+    fn create_contexts() {
+        for platform in cl_get_platform_ids() {
+            let devices = cl_get_device_ids(platform, DeviceType::ALL).unwrap();
+            if devices.is_empty() {
+                continue;
+            }
+
+            let context = Context::new(platform, &devices).unwrap();
+            assert_eq!(context.platform(), platform);
+            assert_eq!(context.devices(), devices.as_slice());
+
+            let copy = context.clone();
+            assert_eq!(copy.id(), context.id());
+            assert!(context.reference_count().unwrap() >= 2);
+
+            let context = Context::from_type(platform, DeviceType::ALL).unwrap();
+            assert_eq!(context.devices().len(), devices.len());
+        }
+    }
+
+    #[test]
+    fn forward_notifications() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sender = std::sync::Mutex::new(sender);
+        let notify: Notify = Box::new(move |message, private_info| {
+            sender.lock().unwrap().send((message.to_string(), private_info.to_vec())).unwrap();
+        });
+
+        let user_data = &notify as *const Notify as *mut c_void;
+        context_notify(c"out of memory".as_ptr(), [1u8, 2].as_ptr() as *const c_void, 2, user_data);
+        context_notify(std::ptr::null(), std::ptr::null(), 0, user_data);
+        assert_eq!(receiver.recv().unwrap(), ("out of memory".to_string(), vec![1, 2]));
+        assert_eq!(receiver.recv().unwrap(), (String::new(), vec![]));
+    }
+
+    #[test]
+    fn create_contexts_with_notify() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type_with_notify(platform, DeviceType::ALL, |message, _| eprintln!("{}", message)) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+            let copy = context.clone();
+            drop(context);
+            assert!(copy.reference_count().unwrap() >= 1);
+
+            let context = Context::with_notify(platform, copy.devices(), |_, _| {}).unwrap();
+            assert_eq!(context.devices(), copy.devices());
+        }
+    }
+
+    #[test]
+    fn empty_device_list() {
+        assert_eq!(Context::new(0, &[]).err(), Some(Error::InvalidValue));
+    }
+}
@@ -1,6 +1,7 @@
 use std::ffi::c_void;
-use crate::native::{clGetDeviceIDs, clGetDeviceInfo};
-use crate::types::{DeviceId, DeviceInfo, DeviceType, PlatformId};
+use crate::info;
+use crate::native::{clCreateSubDevices, clGetDeviceIDs, clGetDeviceInfo, clReleaseDevice, clRetainDevice, NameVersion};
+use crate::types::{AffinityDomain, DeviceId, DeviceInfo, DeviceType, PartitionKind, PartitionProperty, PartitionScheme, PlatformId};
 use crate::result::{Result, Error};
 
 /// Get all available device IDs with the given device type on the given platform.
@@ -62,6 +63,15 @@ pub unsafe fn cl_get_device_info<T: Sized>(device: DeviceId, name: DeviceInfo) -
     Ok(value.assume_init())
 }
 
+/// Get an array device info for the given device.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the element type of the device info.
+pub unsafe fn cl_get_device_info_vec<T: Copy>(device: DeviceId, name: DeviceInfo) -> Result<Vec<T>> {
+    info::get_vec(|size, value, size_ret| clGetDeviceInfo(device, name, size, value, size_ret))
+}
+
 /// Get a device info for the given device.
 ///
 /// # Safety
@@ -83,3 +93,267 @@ pub unsafe fn cl_get_device_info_string(device: DeviceId, name: DeviceInfo) -> R
     value.truncate(value.len() - 1);
     Ok(std::str::from_utf8_unchecked(value.as_slice()).to_string())
 }
+
+/// Get the intermediate languages supported by the given device, e.g. `SPIR-V_1.2`.
+///
+/// `DeviceInfo::IlsWithVersion` is used on OpenCL 3.0 devices, otherwise `DeviceInfo::IlVersion` is parsed.
+/// Devices without intermediate language support yield an empty list.
+///
+/// # Errors
+///
+/// The following errors may be returned:
+///
+/// * `Error::InvalidDevice` - An invalid device was passed.
+pub fn cl_get_device_ils(device: DeviceId) -> Result<Vec<String>> {
+    if let Ok(ils) = unsafe { cl_get_device_info_vec::<NameVersion>(device, DeviceInfo::IlsWithVersion) } {
+        return Ok(ils.iter().map(decode_name_version).collect());
+    }
+
+    match unsafe { cl_get_device_info_string(device, DeviceInfo::IlVersion) } {
+        Ok(ils) => Ok(ils.split_whitespace().map(String::from).collect()),
+        Err(Error::InvalidDevice) => Err(Error::InvalidDevice),
+        Err(_) => Ok(vec![]),
+    }
+}
+
+/// Get the names of the built-in kernels of the given device.
+///
+/// Devices without built-in kernels yield an empty list.
+///
+/// # Errors
+///
+/// The following errors may be returned:
+///
+/// * `Error::InvalidDevice` - An invalid device was passed.
+pub fn cl_get_device_built_in_kernels(device: DeviceId) -> Result<Vec<String>> {
+    let names = unsafe {
+        info::get_string(|size, value, size_ret| clGetDeviceInfo(device, DeviceInfo::BuiltInKernels, size, value, size_ret))
+    }?;
+    Ok(parse_kernel_names(&names))
+}
+
+/// Split a semicolon separated list of kernel names.
+fn parse_kernel_names(names: &str) -> Vec<String> {
+    names.split(';').map(str::trim).filter(|name| !name.is_empty()).map(String::from).collect()
+}
+
+/// Format a `cl_name_version` in the style of `DeviceInfo::IlVersion`, e.g. `SPIR-V_1.2`.
+fn decode_name_version(value: &NameVersion) -> String {
+    let name: Vec<u8> = value.name.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+    let major = value.version >> 22;
+    let minor = (value.version >> 12) & 0x3FF;
+    format!("{}_{}.{}", String::from_utf8_lossy(&name), major, minor)
+}
+
+/// Structure containing the partitioning capabilities of a device and how it was partitioned.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+pub struct PartitionInfo {
+    max_sub_devices: u32,
+    kinds: Vec<PartitionKind>,
+    affinity_domains: AffinityDomain,
+    parent: Option<DeviceId>,
+    scheme: Option<PartitionScheme>,
+}
+
+impl PartitionInfo {
+    /// Get the partition information for the given device.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidDevice` - An invalid device was passed.
+    /// * `Error::InvalidValue` - The device does not support OpenCL 1.2.
+    pub fn get(device: DeviceId) -> Result<Self> {
+        let kinds: Vec<PartitionProperty> = unsafe { cl_get_device_info_vec(device, DeviceInfo::PartitionProperties) }?;
+        let scheme: Vec<PartitionProperty> = unsafe { cl_get_device_info_vec(device, DeviceInfo::PartitionType) }?;
+        let parent: DeviceId = unsafe { cl_get_device_info(device, DeviceInfo::ParentDevice) }?;
+        Ok(Self {
+            max_sub_devices: unsafe { cl_get_device_info(device, DeviceInfo::PartitionMaxSubDevices) }?,
+            kinds: kinds.into_iter().filter_map(|kind| PartitionKind::try_from(kind).ok()).collect(),
+            affinity_domains: AffinityDomain::from_raw(unsafe { cl_get_device_info(device, DeviceInfo::PartitionAffinityDomain) }?),
+            parent: if parent == 0 { None } else { Some(parent) },
+            scheme: PartitionScheme::from_properties(&scheme),
+        })
+    }
+
+    /// The maximum number of sub-devices the device can be partitioned into.
+    pub fn max_sub_devices(&self) -> u32 {
+        self.max_sub_devices
+    }
+
+    /// The supported kinds of partition schemes. Empty if the device cannot be partitioned.
+    pub fn kinds(&self) -> &[PartitionKind] {
+        &self.kinds
+    }
+
+    /// The affinity domains the device can be partitioned along.
+    pub fn affinity_domains(&self) -> AffinityDomain {
+        self.affinity_domains
+    }
+
+    /// The device this device was partitioned from, `None` for root devices.
+    pub fn parent(&self) -> Option<DeviceId> {
+        self.parent
+    }
+
+    /// The scheme this device was partitioned with, `None` for root devices.
+    pub fn scheme(&self) -> Option<&PartitionScheme> {
+        self.scheme.as_ref()
+    }
+
+    /// Check whether the device can be partitioned with the given scheme.
+    fn validate(&self, scheme: &PartitionScheme) -> Result<()> {
+        if !self.kinds.contains(&scheme.kind()) {
+            return Err(Error::InvalidValue);
+        }
+        match scheme {
+            PartitionScheme::Equally(0) => Err(Error::InvalidDevicePartitionCount),
+            PartitionScheme::ByCounts(counts) if counts.is_empty() || counts.len() > self.max_sub_devices as usize || counts.contains(&0) => {
+                Err(Error::InvalidDevicePartitionCount)
+            }
+            PartitionScheme::ByAffinityDomain(domain) if domain.raw().count_ones() != 1 || !self.affinity_domains.contains(*domain) => {
+                Err(Error::InvalidValue)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A sub-device created by partitioning a device.
+///
+/// The sub-device is retained when cloned and released when dropped.
+pub struct SubDevice {
+    id: DeviceId,
+}
+
+impl SubDevice {
+    /// Partition the given device into sub-devices.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device to partition. May be a sub-device itself.
+    /// * `scheme` - How the compute units of the device are distributed over the sub-devices.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidDevice` - An invalid device was passed.
+    /// * `Error::InvalidValue` - The scheme or affinity domain is not supported by the device.
+    /// * `Error::InvalidDevicePartitionCount` - The scheme requests no compute units or too many sub-devices.
+    /// * `Error::DevicePartitionFailed` - The scheme is supported, but the device could not be partitioned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::device::{cl_get_device_ids, SubDevice};
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::types::{AffinityDomain, DeviceType, PartitionScheme};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let cpu = cl_get_device_ids(Platform::default().id(), DeviceType::CPU)?[0];
+    /// let nodes = SubDevice::create(cpu, &PartitionScheme::ByAffinityDomain(AffinityDomain::NUMA))?;
+    /// println!("{} NUMA nodes", nodes.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create(device: DeviceId, scheme: &PartitionScheme) -> Result<Vec<SubDevice>> {
+        PartitionInfo::get(device)?.validate(scheme)?;
+
+        let properties = scheme.to_properties();
+        let mut num_devices: u32 = 0;
+        let result = unsafe { clCreateSubDevices(device, properties.as_ptr(), 0, std::ptr::null_mut(), &mut num_devices) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        let mut ids: Vec<DeviceId> = vec![0; num_devices as usize];
+        let result = unsafe { clCreateSubDevices(device, properties.as_ptr(), num_devices, ids.as_mut_ptr(), std::ptr::null_mut()) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(ids.into_iter().map(|id| SubDevice { id }).collect())
+    }
+
+    /// The ID of the sub-device.
+    pub fn id(&self) -> DeviceId {
+        self.id
+    }
+}
+
+impl Clone for SubDevice {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainDevice(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id: self.id }
+    }
+}
+
+impl Drop for SubDevice {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseDevice(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_name_versions() {
+        let mut value = NameVersion { version: (1 << 22) | (2 << 12), name: [0; 64] };
+        for (i, c) in b"SPIR-V".iter().enumerate() {
+            value.name[i] = *c as std::ffi::c_char;
+        }
+        assert_eq!(decode_name_version(&value), "SPIR-V_1.2");
+    }
+
+    #[test]
+    fn parse_built_in_kernels() {
+        assert!(parse_kernel_names("").is_empty());
+        assert_eq!(parse_kernel_names("copy"), vec!["copy"]);
+        assert_eq!(parse_kernel_names("intel_sha1; intel_sha256;"), vec!["intel_sha1", "intel_sha256"]);
+    }
+
+    #[test]
+    fn partition_properties() {
+        let schemes = [
+            (PartitionScheme::Equally(4), vec![0x1086, 4, 0]),
+            (PartitionScheme::ByCounts(vec![2, 6]), vec![0x1087, 2, 6, 0, 0]),
+            (PartitionScheme::ByAffinityDomain(AffinityDomain::NUMA), vec![0x1088, 1, 0]),
+        ];
+        for (scheme, properties) in schemes {
+            assert_eq!(scheme.to_properties(), properties);
+            assert_eq!(PartitionScheme::from_properties(&properties), Some(scheme));
+        }
+        assert_eq!(PartitionScheme::from_properties(&[]), None);
+        assert_eq!(PartitionScheme::from_properties(&[0]), None);
+    }
+
+    #[test]
+    fn validate_partitions() {
+        let info = PartitionInfo {
+            max_sub_devices: 8,
+            kinds: vec![PartitionKind::Equally, PartitionKind::ByCounts],
+            affinity_domains: AffinityDomain::NONE,
+            parent: None,
+            scheme: None,
+        };
+        assert!(info.validate(&PartitionScheme::Equally(2)).is_ok());
+        assert_eq!(info.validate(&PartitionScheme::Equally(0)), Err(Error::InvalidDevicePartitionCount));
+        assert!(info.validate(&PartitionScheme::ByCounts(vec![1, 7])).is_ok());
+        assert_eq!(info.validate(&PartitionScheme::ByCounts(vec![])), Err(Error::InvalidDevicePartitionCount));
+        assert_eq!(info.validate(&PartitionScheme::ByCounts(vec![1; 9])), Err(Error::InvalidDevicePartitionCount));
+        assert_eq!(info.validate(&PartitionScheme::ByAffinityDomain(AffinityDomain::NUMA)), Err(Error::InvalidValue));
+
+        let info = PartitionInfo { kinds: vec![PartitionKind::ByAffinityDomain], affinity_domains: AffinityDomain::NUMA | AffinityDomain::L2_CACHE, ..info };
+        assert!(info.validate(&PartitionScheme::ByAffinityDomain(AffinityDomain::L2_CACHE)).is_ok());
+        assert_eq!(info.validate(&PartitionScheme::ByAffinityDomain(AffinityDomain::L1_CACHE)), Err(Error::InvalidValue));
+        assert_eq!(info.validate(&PartitionScheme::ByAffinityDomain(AffinityDomain::NUMA | AffinityDomain::L2_CACHE)), Err(Error::InvalidValue));
+        assert_eq!(info.validate(&PartitionScheme::Equally(2)), Err(Error::InvalidValue));
+    }
+}
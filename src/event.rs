@@ -0,0 +1,438 @@
+use std::ffi::c_void;
+use std::time::Duration;
+use crate::context::Context;
+use crate::device::cl_get_device_info;
+use crate::info;
+use crate::native::{clCreateUserEvent, clGetEventInfo, clGetEventProfilingInfo, clReleaseEvent, clRetainEvent, clSetEventCallback, clSetUserEventStatus, clWaitForEvents};
+use crate::queue::cl_get_command_queue_info;
+use crate::result::{Error, Result};
+use crate::types::{CommandQueueId, CommandQueueInfo, CommandQueueProperties, DeviceId, DeviceInfo, EventId, EventInfo, ExecutionStatus, ProfilingInfo};
+
+/// Decode an execution status, turning negative error codes into errors.
+fn decode_status(status: i32) -> Result<ExecutionStatus> {
+    ExecutionStatus::try_from(status).map_err(Error::from)
+}
+
+/// Get the length and pointer of a wait list as expected by the `clEnqueue*` functions.
+pub(crate) fn wait_list(events: &[Event]) -> (u32, *const EventId) {
+    if events.is_empty() {
+        (0, std::ptr::null())
+    } else {
+        (events.len() as u32, events.as_ptr() as *const EventId)
+    }
+}
+
+/// The profiling timestamps of a command.
+///
+/// The timestamps are device time counters relative to an implementation defined epoch.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Timing {
+    queued: Duration,
+    submit: Duration,
+    start: Duration,
+    end: Duration,
+    complete: Option<Duration>,
+    resolution: Duration,
+}
+
+impl Timing {
+    /// When the command was enqueued in the command queue by the host.
+    pub fn queued(&self) -> Duration {
+        self.queued
+    }
+
+    /// When the command was submitted by the host to the device.
+    pub fn submit(&self) -> Duration {
+        self.submit
+    }
+
+    /// When the command started execution on the device.
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+
+    /// When the command finished execution on the device.
+    pub fn end(&self) -> Duration {
+        self.end
+    }
+
+    /// When the command and all its child commands finished execution.
+    ///
+    /// Only available on OpenCL 2.0 or newer.
+    pub fn complete(&self) -> Option<Duration> {
+        self.complete
+    }
+
+    /// The resolution of the device timer (`DeviceInfo::ProfilingTimerResolution`).
+    ///
+    /// The timestamps are not more precise than this.
+    pub fn resolution(&self) -> Duration {
+        self.resolution
+    }
+
+    /// The time the command spent executing on the device.
+    pub fn execution_time(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// The time from enqueueing the command until it finished execution.
+    pub fn total_time(&self) -> Duration {
+        self.end.saturating_sub(self.queued)
+    }
+}
+
+/// An OpenCL event identifying a command enqueued on a command queue.
+///
+/// The event is retained when cloned and released when dropped.
+#[repr(transparent)]
+pub struct Event {
+    id: EventId,
+}
+
+type Callback = Box<dyn FnOnce(Result<ExecutionStatus>) + Send>;
+
+extern "C" fn event_callback(_event: EventId, status: i32, user_data: *mut c_void) {
+    let callback = unsafe { Box::from_raw(user_data as *mut Callback) };
+    // Unwinding into the OpenCL runtime is undefined behavior.
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| callback(decode_status(status))));
+}
+
+impl Event {
+    /// Take ownership of an event handle returned by OpenCL.
+    pub(crate) fn from_id(id: EventId) -> Self {
+        Self { id }
+    }
+
+    /// The native handle of the event.
+    pub fn id(&self) -> EventId {
+        self.id
+    }
+
+    /// Block until the command identified by the event has completed.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - The command terminated abnormally.
+    pub fn wait(&self) -> Result<()> {
+        Event::wait_all(std::slice::from_ref(self))
+    }
+
+    /// Block until the commands identified by all given events have completed.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidContext` - The events do not belong to the same context.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - One of the commands terminated abnormally.
+    pub fn wait_all(events: &[Event]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let (num_events, event_list) = wait_list(events);
+        let result = unsafe { clWaitForEvents(num_events, event_list) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+
+    /// The execution status of the command identified by the event.
+    ///
+    /// # Errors
+    ///
+    /// If the command terminated abnormally, the error code it terminated with is returned.
+    pub fn status(&self) -> Result<ExecutionStatus> {
+        let status: i32 = unsafe {
+            info::get_value(|size, value, size_ret| clGetEventInfo(self.id, EventInfo::CommandExecutionStatus, size, value, size_ret))
+        }?;
+        decode_status(status)
+    }
+
+    /// The current reference count of the event.
+    ///
+    /// # Remarks
+    ///
+    /// The reference count should be considered immediately stale.
+    /// It is only useful for identifying memory leaks.
+    pub fn reference_count(&self) -> Result<u32> {
+        unsafe { info::get_value(|size, value, size_ret| clGetEventInfo(self.id, EventInfo::ReferenceCount, size, value, size_ret)) }
+    }
+
+    /// The native handle of the command queue the command was enqueued on.
+    ///
+    /// User events are not associated with a command queue and return `0`.
+    pub fn command_queue_id(&self) -> Result<CommandQueueId> {
+        unsafe { info::get_value(|size, value, size_ret| clGetEventInfo(self.id, EventInfo::CommandQueue, size, value, size_ret)) }
+    }
+
+    /// Get a profiling timestamp of the command identified by the event.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::ProfilingInfoNotAvailable` - The command queue does not have profiling enabled,
+    ///   the command has not completed yet or the event is a user event.
+    pub fn profiling_info(&self, name: ProfilingInfo) -> Result<Duration> {
+        self.check_profiling()?;
+        self.query_profiling_info(name)
+    }
+
+    /// Get all profiling timestamps of the command identified by the event,
+    /// together with the resolution of the device timer.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::ProfilingInfoNotAvailable` - The command queue does not have profiling enabled,
+    ///   the command has not completed yet or the event is a user event.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::PROFILING_ENABLE)?;
+    /// let buffer = Buffer::<u8>::new(&context, MemFlags::READ_WRITE, 1 << 20)?;
+    ///
+    /// let event = buffer.write(&queue, 0, &vec![42u8; 1 << 20], &[])?;
+    /// let timing = event.timing()?;
+    /// println!("Write took {:?} (±{:?})", timing.execution_time(), timing.resolution());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn timing(&self) -> Result<Timing> {
+        let queue = self.check_profiling()?;
+        let device: DeviceId = unsafe { cl_get_command_queue_info(queue, CommandQueueInfo::Device) }?;
+        let resolution: usize = unsafe { cl_get_device_info(device, DeviceInfo::ProfilingTimerResolution) }?;
+
+        Ok(Timing {
+            queued: self.query_profiling_info(ProfilingInfo::Queued)?,
+            submit: self.query_profiling_info(ProfilingInfo::Submit)?,
+            start: self.query_profiling_info(ProfilingInfo::Start)?,
+            end: self.query_profiling_info(ProfilingInfo::End)?,
+            complete: self.query_profiling_info(ProfilingInfo::Complete).ok(),
+            resolution: Duration::from_nanos(resolution as u64),
+        })
+    }
+
+    /// Make sure the command was enqueued on a queue with profiling enabled, returning that queue.
+    fn check_profiling(&self) -> Result<CommandQueueId> {
+        let queue = self.command_queue_id()?;
+        if queue == 0 {
+            return Err(Error::ProfilingInfoNotAvailable);
+        }
+
+        let properties: u64 = unsafe { cl_get_command_queue_info(queue, CommandQueueInfo::Properties) }?;
+        if !CommandQueueProperties::from_raw(properties).contains(CommandQueueProperties::PROFILING_ENABLE) {
+            return Err(Error::ProfilingInfoNotAvailable);
+        }
+        Ok(queue)
+    }
+
+    fn query_profiling_info(&self, name: ProfilingInfo) -> Result<Duration> {
+        let nanos: u64 = unsafe {
+            info::get_value(|size, value, size_ret| clGetEventProfilingInfo(self.id, name, size, value, size_ret))
+        }?;
+        Ok(Duration::from_nanos(nanos))
+    }
+
+    /// Register a callback which is called once the command reaches the given execution status.
+    ///
+    /// The callback is also called if the command terminates abnormally, in which case it receives the error code.
+    /// It is called from a thread of the OpenCL implementation and must not call blocking OpenCL functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The execution status to wait for. Must not be `ExecutionStatus::Queued`.
+    /// * `callback` - The function to call.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - `ExecutionStatus::Queued` was passed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, ExecutionStatus, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    /// let buffer = Buffer::<u8>::new(&context, MemFlags::READ_WRITE, 1 << 20)?;
+    ///
+    /// let data = vec![42u8; 1 << 20];
+    /// let event = buffer.write(&queue, 0, &data, &[])?;
+    /// event.set_callback(ExecutionStatus::Complete, |status| println!("Write finished: {:?}", status))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_callback<F>(&self, status: ExecutionStatus, callback: F) -> Result<()>
+    where
+        F: FnOnce(Result<ExecutionStatus>) + Send + 'static,
+    {
+        if status == ExecutionStatus::Queued {
+            return Err(Error::InvalidValue);
+        }
+
+        let callback: *mut Callback = Box::into_raw(Box::new(Box::new(callback)));
+        let result = unsafe { clSetEventCallback(self.id, status as i32, Some(event_callback), callback as *mut c_void) };
+        if result != 0 {
+            drop(unsafe { Box::from_raw(callback) });
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+}
+
+impl Clone for Event {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainEvent(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id: self.id }
+    }
+}
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseEvent(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+/// An event whose execution status is set by the host.
+///
+/// Commands waiting on a user event are held back until the host completes it,
+/// which allows gating device work on host-side conditions like I/O.
+/// The status can only be set once. Use [UserEvent::event] to put it into a wait list.
+///
+/// Make sure to set the status before dropping the last reference to commands waiting on it,
+/// otherwise they never execute.
+#[derive(Clone)]
+pub struct UserEvent {
+    event: Event,
+}
+
+impl UserEvent {
+    /// Create a user event in the given context, with the execution status `ExecutionStatus::Submitted`.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidContext` - An invalid context was passed.
+    pub fn new(context: &Context) -> Result<Self> {
+        let mut result: i32 = 0;
+        let id = unsafe { clCreateUserEvent(context.id(), &mut result) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { event: Event::from_id(id) })
+    }
+
+    /// The underlying event, e.g. for use in wait lists.
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Mark the user event as complete, releasing the commands waiting on it.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidOperation` - The status of the user event has already been set.
+    pub fn complete(&self) -> Result<()> {
+        self.set_status(ExecutionStatus::Complete as i32)
+    }
+
+    /// Mark the user event as terminated with the given error.
+    ///
+    /// Commands waiting on the user event will not execute and fail with
+    /// `Error::ExecStatusErrorForEventsInWaitList`.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - `Error::Unknown` was passed, which has no negative error code.
+    /// * `Error::InvalidOperation` - The status of the user event has already been set.
+    pub fn fail(&self, error: Error) -> Result<()> {
+        if error as i32 >= 0 {
+            return Err(Error::InvalidValue);
+        }
+        self.set_status(error as i32)
+    }
+
+    fn set_status(&self, status: i32) -> Result<()> {
+        let result = unsafe { clSetUserEventStatus(self.event.id, status) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for UserEvent {
+    type Target = Event;
+
+    fn deref(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl From<UserEvent> for Event {
+    fn from(user_event: UserEvent) -> Self {
+        user_event.event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_statuses() {
+        assert_eq!(decode_status(0), Ok(ExecutionStatus::Complete));
+        assert_eq!(decode_status(3), Ok(ExecutionStatus::Queued));
+        assert_eq!(decode_status(-5), Err(Error::OutOfResources));
+        assert_eq!(decode_status(-1234), Err(Error::Unknown));
+    }
+
+    #[test]
+    fn timing_durations() {
+        let timing = Timing {
+            queued: Duration::from_nanos(1_000),
+            submit: Duration::from_nanos(1_500),
+            start: Duration::from_nanos(2_000),
+            end: Duration::from_nanos(5_000),
+            complete: None,
+            resolution: Duration::from_nanos(1),
+        };
+        assert_eq!(timing.execution_time(), Duration::from_nanos(3_000));
+        assert_eq!(timing.total_time(), Duration::from_nanos(4_000));
+    }
+
+    #[test]
+    fn empty_wait_list() {
+        assert_eq!(wait_list(&[]), (0, std::ptr::null()));
+        assert_eq!(Event::wait_all(&[]), Ok(()));
+    }
+}
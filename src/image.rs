@@ -0,0 +1,788 @@
+use std::ffi::c_void;
+use std::ops::Deref;
+use crate::context::Context;
+use crate::device::cl_get_device_info;
+use crate::event::{self, Event};
+use crate::info;
+use crate::kernel::{ArgInfo, KernelArg};
+use crate::memory::{check_write_flags, MapGuard, MapReadGuard, MappedRegions, Mapping, MemObject};
+use crate::native::{clCreateImage, clEnqueueFillImage, clEnqueueMapImage, clEnqueueReadImage, clEnqueueWriteImage, clGetImageInfo, clGetSupportedImageFormats, clReleaseMemObject, clRetainMemObject, ImageDesc, RawImageFormat};
+use crate::queue::CommandQueue;
+use crate::result::{Error, Result};
+use crate::types::{AddressQualifier, ChannelOrder, ChannelType, DeviceId, DeviceInfo, EventId, ImageFormat, ImageInfo, MapFlags, MemFlags, MemId, MemObjectType, Pod};
+
+/// Get the image formats supported by the given context for the given image type and flags.
+///
+/// Formats using channel orders or data types unknown to this library are skipped.
+///
+/// # Errors
+///
+/// The following errors may be returned:
+///
+/// * `Error::InvalidContext` - An invalid context was passed.
+/// * `Error::InvalidValue` - The flags or the image type are invalid.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use rusty_cl::context::Context;
+/// # use rusty_cl::image::supported_image_formats;
+/// # use rusty_cl::platform::Platform;
+/// # use rusty_cl::types::{DeviceType, MemFlags, MemObjectType};
+/// # fn main() -> rusty_cl::result::Result<()> {
+/// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+/// for format in supported_image_formats(&context, MemFlags::READ_ONLY, MemObjectType::Image2D)? {
+///     println!("{:?} {:?}", format.channel_order, format.channel_data_type);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn supported_image_formats(context: &Context, flags: MemFlags, image_type: MemObjectType) -> Result<Vec<ImageFormat>> {
+    let mut num_formats: u32 = 0;
+    let result = unsafe {
+        clGetSupportedImageFormats(context.id(), flags.raw(), image_type as u32, 0, std::ptr::null_mut(), &mut num_formats)
+    };
+    if result != 0 {
+        return Err(Error::from(result));
+    }
+
+    let mut formats: Vec<RawImageFormat> = vec![RawImageFormat::default(); num_formats as usize];
+    if num_formats == 0 {
+        return Ok(vec![]);
+    }
+
+    let result = unsafe {
+        clGetSupportedImageFormats(context.id(), flags.raw(), image_type as u32, num_formats, formats.as_mut_ptr(), std::ptr::null_mut())
+    };
+    if result != 0 {
+        return Err(Error::from(result));
+    }
+
+    Ok(formats.into_iter().filter_map(|format| decode_format(&format)).collect())
+}
+
+fn decode_format(format: &RawImageFormat) -> Option<ImageFormat> {
+    Some(ImageFormat {
+        channel_order: ChannelOrder::try_from(format.channel_order).ok()?,
+        channel_data_type: ChannelType::try_from(format.channel_data_type).ok()?,
+    })
+}
+
+/// Get an image info for the given image.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the type of the image info.
+pub unsafe fn cl_get_image_info<T: Sized>(image: MemId, name: ImageInfo) -> Result<T> {
+    info::get_value(|size, value, size_ret| clGetImageInfo(image, name, size, value, size_ret))
+}
+
+/// The color an image is filled with, which must match the channel data type of the image.
+///
+/// Colors are given as `[r, g, b, a]` and converted to the channel order and data type of the image.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum FillColor {
+    /// A color for normalized, half and float channel data types.
+    Float([f32; 4]),
+    /// A color for signed integer channel data types.
+    Int([i32; 4]),
+    /// A color for unsigned integer channel data types.
+    UInt([u32; 4]),
+}
+
+impl FillColor {
+    /// Whether the color can be used for images with the given channel data type.
+    fn matches(&self, channel_type: ChannelType) -> bool {
+        match channel_type {
+            ChannelType::SignedInt8 | ChannelType::SignedInt16 | ChannelType::SignedInt32 => matches!(self, FillColor::Int(_)),
+            ChannelType::UnsignedInt8 | ChannelType::UnsignedInt16 | ChannelType::UnsignedInt32 => matches!(self, FillColor::UInt(_)),
+            _ => matches!(self, FillColor::Float(_)),
+        }
+    }
+
+    /// Pointer to the four color components.
+    fn as_ptr(&self) -> *const c_void {
+        match self {
+            FillColor::Float(color) => color.as_ptr() as *const c_void,
+            FillColor::Int(color) => color.as_ptr() as *const c_void,
+            FillColor::UInt(color) => color.as_ptr() as *const c_void,
+        }
+    }
+}
+
+/// The image limits of a device.
+struct ImageLimits {
+    max_2d: [usize; 2],
+    max_3d: [usize; 3],
+    max_array_size: usize,
+}
+
+impl ImageLimits {
+    /// Query the image limits of a device, returning `None` if the device does not support images.
+    fn query(device: DeviceId) -> Result<Option<Self>> {
+        unsafe {
+            let image_support: u32 = cl_get_device_info(device, DeviceInfo::ImageSupport)?;
+            if image_support == 0 {
+                return Ok(None);
+            }
+
+            Ok(Some(Self {
+                max_2d: [
+                    cl_get_device_info(device, DeviceInfo::Image2dMaxWidth)?,
+                    cl_get_device_info(device, DeviceInfo::Image2dMaxHeight)?,
+                ],
+                max_3d: [
+                    cl_get_device_info(device, DeviceInfo::Image3dMaxWidth)?,
+                    cl_get_device_info(device, DeviceInfo::Image3dMaxHeight)?,
+                    cl_get_device_info(device, DeviceInfo::Image3dMaxDepth)?,
+                ],
+                max_array_size: cl_get_device_info(device, DeviceInfo::ImageMaxArraySize).unwrap_or(0),
+            }))
+        }
+    }
+
+    /// Whether an image of the given type and extent can be created.
+    fn allows(&self, image_type: MemObjectType, extent: [usize; 3]) -> bool {
+        let [width, height, depth] = extent;
+        match image_type {
+            MemObjectType::Image1D | MemObjectType::Image1DBuffer => width <= self.max_2d[0],
+            MemObjectType::Image1DArray => width <= self.max_2d[0] && height <= self.max_array_size,
+            MemObjectType::Image2D => width <= self.max_2d[0] && height <= self.max_2d[1],
+            MemObjectType::Image2DArray => width <= self.max_2d[0] && height <= self.max_2d[1] && depth <= self.max_array_size,
+            MemObjectType::Image3D => width <= self.max_3d[0] && height <= self.max_3d[1] && depth <= self.max_3d[2],
+            MemObjectType::Buffer | MemObjectType::Pipe => false,
+        }
+    }
+}
+
+/// An OpenCL image of any type.
+///
+/// The extent of the image is given as `[width, height, depth]`, where the number of images in an
+/// image array takes the place of the height (1D arrays) or depth (2D arrays), just like the
+/// origin and region of read and write operations.
+///
+/// The image is retained when cloned and released when dropped.
+pub struct Image {
+    id: MemId,
+    context: Context,
+    image_type: MemObjectType,
+    format: ImageFormat,
+    extent: [usize; 3],
+    mappings: MappedRegions,
+}
+
+impl Image {
+    /// Create an image after validating it against the limits of the devices in the context.
+    ///
+    /// # Safety
+    ///
+    /// If `host_ptr` is not null, it must point to enough data for the whole image.
+    /// If `flags` contains `USE_HOST_PTR`, that data must outlive the image.
+    unsafe fn create(
+        context: &Context, flags: MemFlags, format: ImageFormat, image_type: MemObjectType, extent: [usize; 3], host_ptr: *mut c_void,
+    ) -> Result<Self> {
+        if extent.contains(&0) {
+            return Err(Error::InvalidImageSize);
+        }
+
+        let mut any_support = false;
+        for &device in context.devices() {
+            if let Some(limits) = ImageLimits::query(device)? {
+                any_support = true;
+                if !limits.allows(image_type, extent) {
+                    return Err(Error::InvalidImageSize);
+                }
+            }
+        }
+        if !any_support {
+            return Err(Error::InvalidOperation);
+        }
+
+        let [width, height, depth] = extent;
+        let mut desc = ImageDesc { image_type: image_type as u32, image_width: width, ..ImageDesc::default() };
+        match image_type {
+            MemObjectType::Image1DArray => desc.image_array_size = height,
+            MemObjectType::Image2D => desc.image_height = height,
+            MemObjectType::Image2DArray => {
+                desc.image_height = height;
+                desc.image_array_size = depth;
+            }
+            MemObjectType::Image3D => {
+                desc.image_height = height;
+                desc.image_depth = depth;
+            }
+            _ => {}
+        }
+        let raw_format = RawImageFormat {
+            channel_order: format.channel_order as u32,
+            channel_data_type: format.channel_data_type as u32,
+        };
+
+        let mut result: i32 = 0;
+        let id = clCreateImage(context.id(), flags.raw(), &raw_format, &desc, host_ptr, &mut result);
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone(), image_type, format, extent, mappings: MappedRegions::default() })
+    }
+
+    /// Create an image, optionally initialized with a copy of the given pixel data.
+    fn with_data<T: Pod>(
+        context: &Context, flags: MemFlags, format: ImageFormat, image_type: MemObjectType, extent: [usize; 3], data: Option<&[T]>,
+    ) -> Result<Self> {
+        if flags.contains(MemFlags::USE_HOST_PTR) || flags.contains(MemFlags::COPY_HOST_PTR) {
+            return Err(Error::InvalidValue);
+        }
+        match data {
+            Some(data) => {
+                let size = extent.iter().product::<usize>() * format.element_size();
+                if std::mem::size_of_val(data) != size {
+                    return Err(Error::InvalidHostPtr);
+                }
+                unsafe { Self::create(context, flags | MemFlags::COPY_HOST_PTR, format, image_type, extent, data.as_ptr() as *mut c_void) }
+            }
+            None => unsafe { Self::create(context, flags, format, image_type, extent, std::ptr::null_mut()) },
+        }
+    }
+
+    /// The native handle of the image.
+    pub fn id(&self) -> MemId {
+        self.id
+    }
+
+    /// The context the image was created in.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// The type of the image.
+    pub fn image_type(&self) -> MemObjectType {
+        self.image_type
+    }
+
+    /// The format of the pixels.
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// The extent of the image as `[width, height, depth]`, with unused dimensions being `1`.
+    pub fn extent(&self) -> [usize; 3] {
+        self.extent
+    }
+
+    /// The row pitch of the image in bytes as chosen by the implementation.
+    pub fn row_pitch(&self) -> Result<usize> {
+        unsafe { cl_get_image_info(self.id, ImageInfo::RowPitch) }
+    }
+
+    /// The slice pitch of the image in bytes as chosen by the implementation.
+    pub fn slice_pitch(&self) -> Result<usize> {
+        unsafe { cl_get_image_info(self.id, ImageInfo::SlicePitch) }
+    }
+
+    /// Make sure the region starting at origin lies within the image and matches the size of the host data.
+    fn check_region(&self, origin: [usize; 3], region: [usize; 3], data_size: usize) -> Result<()> {
+        for ((&origin, &region), &extent) in origin.iter().zip(&region).zip(&self.extent) {
+            match origin.checked_add(region) {
+                Some(end) if region > 0 && end <= extent => {}
+                _ => return Err(Error::InvalidValue),
+            }
+        }
+        if region.iter().product::<usize>() * self.format.element_size() != data_size {
+            return Err(Error::InvalidValue);
+        }
+        Ok(())
+    }
+
+    /// Read a region of the image into tightly packed host memory, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the read on.
+    /// * `origin` - The `[x, y, z]` offset in pixels (or image index) where to start reading.
+    /// * `region` - The `[width, height, depth]` in pixels (or number of images) to read.
+    /// * `data` - Where to store the pixels read. Must match the size of the region exactly.
+    /// * `wait_list` - Events which must complete before the read starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty, out of bounds or does not match the size of `data`.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn read<T: Pod>(&self, queue: &CommandQueue, origin: [usize; 3], region: [usize; 3], data: &mut [T], wait_list: &[Event]) -> Result<Event> {
+        self.check_region(origin, region, std::mem::size_of_val(data))?;
+
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueReadImage(
+                queue.id(), self.id, 1, origin.as_ptr(), region.as_ptr(), 0, 0, data.as_mut_ptr() as *mut c_void,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(Event::from_id(event))
+    }
+
+    /// Write tightly packed host memory into a region of the image, blocking until done.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the write on.
+    /// * `origin` - The `[x, y, z]` offset in pixels (or image index) where to start writing.
+    /// * `region` - The `[width, height, depth]` in pixels (or number of images) to write.
+    /// * `data` - The pixels to write. Must match the size of the region exactly.
+    /// * `wait_list` - Events which must complete before the write starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty, out of bounds or does not match the size of `data`.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn write<T: Pod>(&self, queue: &CommandQueue, origin: [usize; 3], region: [usize; 3], data: &[T], wait_list: &[Event]) -> Result<Event> {
+        self.check_region(origin, region, std::mem::size_of_val(data))?;
+
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueWriteImage(
+                queue.id(), self.id, 1, origin.as_ptr(), region.as_ptr(), 0, 0, data.as_ptr() as *const c_void,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(Event::from_id(event))
+    }
+
+    /// Enqueue filling a region of the image with a color, without a transfer from the host.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The queue to enqueue the fill on.
+    /// * `color` - The color to fill with.
+    /// * `origin` - The `[x, y, z]` offset in pixels (or image index) where to start filling.
+    /// * `region` - The `[width, height, depth]` in pixels (or number of images) to fill.
+    /// * `wait_list` - Events which must complete before the fill starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty or out of bounds, or the color does not match the channel data type.
+    /// * `Error::InvalidContext` - The queue and the image belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::image::{FillColor, Image2D};
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{ChannelOrder, ChannelType, CommandQueueProperties, DeviceType, ImageFormat, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    /// let format = ImageFormat::new(ChannelOrder::RGBA, ChannelType::UnormInt8);
+    /// let image = Image2D::new(&context, MemFlags::READ_WRITE, format, 1920, 1080)?;
+    /// image.fill(&queue, FillColor::Float([0.0, 0.0, 0.0, 1.0]), [0, 0, 0], image.extent(), &[])?.wait()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fill(&self, queue: &CommandQueue, color: FillColor, origin: [usize; 3], region: [usize; 3], wait_list: &[Event]) -> Result<Event> {
+        if !color.matches(self.format.channel_data_type) {
+            return Err(Error::InvalidValue);
+        }
+        self.check_region(origin, region, region.iter().product::<usize>() * self.format.element_size())?;
+
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueFillImage(queue.id(), self.id, color.as_ptr(), origin.as_ptr(), region.as_ptr(), num_events, events, &mut event)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(Event::from_id(event))
+    }
+
+    /// Map a region of the image into host memory, blocking until mapped.
+    fn map<'a, T: Pod>(
+        &self, queue: &'a CommandQueue, flags: MapFlags, origin: [usize; 3], region: [usize; 3], wait_list: &[Event],
+    ) -> Result<Mapping<'a, T>> {
+        self.check_region(origin, region, region.iter().product::<usize>() * std::mem::size_of::<T>())?;
+        let lock = self.mappings.lock(origin, region, check_write_flags(flags).is_ok())?;
+
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut row_pitch: usize = 0;
+        let mut slice_pitch: usize = 0;
+        let mut result: i32 = 0;
+        let ptr = unsafe {
+            clEnqueueMapImage(
+                queue.id(), self.id, 1, flags.raw(), origin.as_ptr(), region.as_ptr(), &mut row_pitch, &mut slice_pitch,
+                num_events, events, std::ptr::null_mut(), &mut result,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        let element_size = std::mem::size_of::<T>();
+        let (row_pitch, slice_pitch) = (row_pitch / element_size, slice_pitch / element_size);
+        let [width, height, depth] = region;
+        let len = match self.image_type {
+            MemObjectType::Image1DArray => (height - 1) * slice_pitch + width,
+            _ => (depth - 1) * slice_pitch + (height - 1) * row_pitch + width,
+        };
+        Ok(unsafe { Mapping::new(self.id, queue, ptr as *mut T, len, row_pitch, slice_pitch, lock) })
+    }
+}
+
+impl MemObject for Image {
+    fn mem_id(&self) -> MemId {
+        self.id
+    }
+}
+
+impl Clone for Image {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainMemObject(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self {
+            id: self.id, context: self.context.clone(), image_type: self.image_type, format: self.format, extent: self.extent,
+            mappings: self.mappings.clone(),
+        }
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseMemObject(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+/// Defines a typed image wrapper around [Image] which can be passed as kernel argument of the given OpenCL C type.
+macro_rules! image_type {
+    ($(#[$attr:meta])* $name:ident, $cl_type:literal) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name(Image);
+
+        impl Deref for $name {
+            type Target = Image;
+
+            fn deref(&self) -> &Image {
+                &self.0
+            }
+        }
+
+        impl $name {
+            /// Map a region of the image into host memory for writing, blocking until mapped.
+            ///
+            /// The guard covers the region with rows and slices spaced by its `row_pitch` and `slice_pitch`
+            /// in pixels, which may exceed the width of the region. It is unmapped when dropped. Until then,
+            /// no overlapping region can be mapped through clones of the image.
+            ///
+            /// # Arguments
+            ///
+            /// * `queue` - The queue to enqueue the map and unmap on.
+            /// * `flags` - Whether the host writes (`WRITE`), also reads (`READ | WRITE`) or completely overwrites
+            ///   (`WRITE_INVALIDATE_REGION`) the region.
+            /// * `origin` - The `[x, y, z]` offset in pixels (or image index) of the region.
+            /// * `region` - The `[width, height, depth]` in pixels (or number of images) of the region.
+            /// * `wait_list` - Events which must complete before the region is mapped.
+            ///
+            /// # Errors
+            ///
+            /// The following errors may be returned:
+            ///
+            /// * `Error::InvalidValue` - The region is empty or out of bounds, the size of `T` differs from the pixel size,
+            ///   or the flags do not request write access.
+            /// * `Error::InvalidOperation` - The flags conflict with the host access flags of the image,
+            ///   or an overlapping region is mapped through another handle.
+            /// * `Error::MapFailure` - The region could not be mapped.
+            /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+            pub fn map<'a, T: Pod>(
+                &'a mut self, queue: &'a CommandQueue, flags: MapFlags, origin: [usize; 3], region: [usize; 3], wait_list: &[Event],
+            ) -> Result<MapGuard<'a, T>> {
+                check_write_flags(flags)?;
+                Ok(MapGuard::new(self.0.map(queue, flags, origin, region, wait_list)?))
+            }
+
+            /// Map a region of the image into host memory for reading, blocking until mapped.
+            ///
+            /// The region is laid out as described for [Self::map] and unmapped when the guard is dropped.
+            /// Until then, overlapping regions can only be mapped for reading through clones of the image.
+            ///
+            /// # Errors
+            ///
+            /// The following errors may be returned:
+            ///
+            /// * `Error::InvalidValue` - The region is empty or out of bounds, or the size of `T` differs from the pixel size.
+            /// * `Error::InvalidOperation` - The image was created with `MemFlags::HOST_WRITE_ONLY` or `HOST_NO_ACCESS`,
+            ///   or an overlapping region is mapped for writing through another handle.
+            /// * `Error::MapFailure` - The region could not be mapped.
+            /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+            pub fn map_read<'a, T: Pod>(
+                &'a mut self, queue: &'a CommandQueue, origin: [usize; 3], region: [usize; 3], wait_list: &[Event],
+            ) -> Result<MapReadGuard<'a, T>> {
+                Ok(MapReadGuard::new(self.0.map(queue, MapFlags::READ, origin, region, wait_list)?))
+            }
+        }
+
+        impl MemObject for $name {
+            fn mem_id(&self) -> MemId {
+                self.0.id
+            }
+        }
+
+        unsafe impl KernelArg for $name {
+            fn size(&self) -> usize {
+                std::mem::size_of::<MemId>()
+            }
+
+            fn value(&self) -> *const c_void {
+                &self.0.id as *const MemId as *const c_void
+            }
+
+            fn matches(&self, info: &ArgInfo) -> bool {
+                info.address_qualifier() == AddressQualifier::Global && info.type_name() == $cl_type
+            }
+
+            fn describe(&self) -> String {
+                format!("an `{}`", $cl_type)
+            }
+
+            fn mem_object(&self) -> Option<&dyn MemObject> {
+                Some(self)
+            }
+        }
+    };
+}
+
+image_type!(
+    /// A 1D image.
+    Image1D, "image1d_t"
+);
+
+image_type!(
+    /// A 2D image.
+    Image2D, "image2d_t"
+);
+
+image_type!(
+    /// A 3D image.
+    Image3D, "image3d_t"
+);
+
+image_type!(
+    /// An array of 1D images.
+    Image1DArray, "image1d_array_t"
+);
+
+image_type!(
+    /// An array of 2D images.
+    Image2DArray, "image2d_array_t"
+);
+
+impl Image1D {
+    /// Create a 1D image.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain `USE_HOST_PTR` or `COPY_HOST_PTR`, which require host data.
+    /// * `Error::InvalidOperation` - No device in the context supports images.
+    /// * `Error::InvalidImageSize` - The width is zero or exceeds `DeviceInfo::Image2dMaxWidth`.
+    /// * `Error::ImageFormatNotSupported` - The image format is not supported.
+    pub fn new(context: &Context, flags: MemFlags, format: ImageFormat, width: usize) -> Result<Self> {
+        Image::with_data::<u8>(context, flags, format, MemObjectType::Image1D, [width, 1, 1], None).map(Self)
+    }
+
+    /// Create a 1D image initialized with a copy of the given pixel data.
+    ///
+    /// Fails with `Error::InvalidHostPtr` if the size of the data does not match the size of the image,
+    /// otherwise the same errors as [Image1D::new] may be returned.
+    pub fn from_slice<T: Pod>(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, data: &[T]) -> Result<Self> {
+        Image::with_data(context, flags, format, MemObjectType::Image1D, [width, 1, 1], Some(data)).map(Self)
+    }
+}
+
+impl Image2D {
+    /// Create a 2D image.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain `USE_HOST_PTR` or `COPY_HOST_PTR`, which require host data.
+    /// * `Error::InvalidOperation` - No device in the context supports images.
+    /// * `Error::InvalidImageSize` - A dimension is zero or exceeds `DeviceInfo::Image2dMaxWidth` or `DeviceInfo::Image2dMaxHeight`.
+    /// * `Error::ImageFormatNotSupported` - The image format is not supported.
+    pub fn new(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, height: usize) -> Result<Self> {
+        Image::with_data::<u8>(context, flags, format, MemObjectType::Image2D, [width, height, 1], None).map(Self)
+    }
+
+    /// Create a 2D image initialized with a copy of the given pixel data.
+    ///
+    /// Fails with `Error::InvalidHostPtr` if the size of the data does not match the size of the image,
+    /// otherwise the same errors as [Image2D::new] may be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::image::Image2D;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{ChannelOrder, ChannelType, CommandQueueProperties, DeviceType, ImageFormat, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    ///
+    /// let format = ImageFormat::new(ChannelOrder::RGBA, ChannelType::UnormInt8);
+    /// let pixels = vec![[255u8, 0, 0, 255]; 640 * 480];
+    /// let image = Image2D::from_slice(&context, MemFlags::READ_ONLY, format, 640, 480, &pixels)?;
+    ///
+    /// let mut row = vec![[0u8; 4]; 640];
+    /// image.read(&queue, [0, 10, 0], [640, 1, 1], &mut row, &[])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_slice<T: Pod>(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, height: usize, data: &[T]) -> Result<Self> {
+        Image::with_data(context, flags, format, MemObjectType::Image2D, [width, height, 1], Some(data)).map(Self)
+    }
+}
+
+impl Image3D {
+    /// Create a 3D image.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain `USE_HOST_PTR` or `COPY_HOST_PTR`, which require host data.
+    /// * `Error::InvalidOperation` - No device in the context supports images.
+    /// * `Error::InvalidImageSize` - A dimension is zero or exceeds `DeviceInfo::Image3dMaxWidth`,
+    ///   `DeviceInfo::Image3dMaxHeight` or `DeviceInfo::Image3dMaxDepth`.
+    /// * `Error::ImageFormatNotSupported` - The image format is not supported.
+    pub fn new(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, height: usize, depth: usize) -> Result<Self> {
+        Image::with_data::<u8>(context, flags, format, MemObjectType::Image3D, [width, height, depth], None).map(Self)
+    }
+
+    /// Create a 3D image initialized with a copy of the given pixel data.
+    ///
+    /// Fails with `Error::InvalidHostPtr` if the size of the data does not match the size of the image,
+    /// otherwise the same errors as [Image3D::new] may be returned.
+    pub fn from_slice<T: Pod>(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, height: usize, depth: usize, data: &[T]) -> Result<Self> {
+        Image::with_data(context, flags, format, MemObjectType::Image3D, [width, height, depth], Some(data)).map(Self)
+    }
+}
+
+impl Image1DArray {
+    /// Create an array of 1D images.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain `USE_HOST_PTR` or `COPY_HOST_PTR`, which require host data.
+    /// * `Error::InvalidOperation` - No device in the context supports images.
+    /// * `Error::InvalidImageSize` - A dimension is zero or exceeds `DeviceInfo::Image2dMaxWidth` or `DeviceInfo::ImageMaxArraySize`.
+    /// * `Error::ImageFormatNotSupported` - The image format is not supported.
+    pub fn new(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, array_size: usize) -> Result<Self> {
+        Image::with_data::<u8>(context, flags, format, MemObjectType::Image1DArray, [width, array_size, 1], None).map(Self)
+    }
+
+    /// Create an array of 1D images initialized with a copy of the given pixel data.
+    ///
+    /// Fails with `Error::InvalidHostPtr` if the size of the data does not match the size of the image array,
+    /// otherwise the same errors as [Image1DArray::new] may be returned.
+    pub fn from_slice<T: Pod>(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, array_size: usize, data: &[T]) -> Result<Self> {
+        Image::with_data(context, flags, format, MemObjectType::Image1DArray, [width, array_size, 1], Some(data)).map(Self)
+    }
+}
+
+impl Image2DArray {
+    /// Create an array of 2D images.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain `USE_HOST_PTR` or `COPY_HOST_PTR`, which require host data.
+    /// * `Error::InvalidOperation` - No device in the context supports images.
+    /// * `Error::InvalidImageSize` - A dimension is zero or exceeds `DeviceInfo::Image2dMaxWidth`,
+    ///   `DeviceInfo::Image2dMaxHeight` or `DeviceInfo::ImageMaxArraySize`.
+    /// * `Error::ImageFormatNotSupported` - The image format is not supported.
+    pub fn new(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, height: usize, array_size: usize) -> Result<Self> {
+        Image::with_data::<u8>(context, flags, format, MemObjectType::Image2DArray, [width, height, array_size], None).map(Self)
+    }
+
+    /// Create an array of 2D images initialized with a copy of the given pixel data.
+    ///
+    /// Fails with `Error::InvalidHostPtr` if the size of the data does not match the size of the image array,
+    /// otherwise the same errors as [Image2DArray::new] may be returned.
+    pub fn from_slice<T: Pod>(context: &Context, flags: MemFlags, format: ImageFormat, width: usize, height: usize, array_size: usize, data: &[T]) -> Result<Self> {
+        Image::with_data(context, flags, format, MemObjectType::Image2DArray, [width, height, array_size], Some(data)).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_sizes() {
+        assert_eq!(ImageFormat::new(ChannelOrder::RGBA, ChannelType::UnormInt8).element_size(), 4);
+        assert_eq!(ImageFormat::new(ChannelOrder::RGBA, ChannelType::Float).element_size(), 16);
+        assert_eq!(ImageFormat::new(ChannelOrder::R, ChannelType::HalfFloat).element_size(), 2);
+        assert_eq!(ImageFormat::new(ChannelOrder::RGB, ChannelType::UnormShort565).element_size(), 2);
+        assert_eq!(ImageFormat::new(ChannelOrder::SRGBx, ChannelType::UnormInt8).element_size(), 4);
+        assert_eq!(ImageFormat::new(ChannelOrder::Rx, ChannelType::Float).element_size(), 8);
+        assert_eq!(ImageFormat::new(ChannelOrder::DepthStencil, ChannelType::UnormInt24).element_size(), 4);
+    }
+
+    #[test]
+    fn decode_formats() {
+        let raw = RawImageFormat { channel_order: 0x10B5, channel_data_type: 0x10DE };
+        assert_eq!(decode_format(&raw), Some(ImageFormat::new(ChannelOrder::RGBA, ChannelType::Float)));
+        let raw = RawImageFormat { channel_order: 0x4000, channel_data_type: 0x10DE };
+        assert_eq!(decode_format(&raw), None);
+    }
+
+    #[test]
+    fn fill_colors() {
+        assert!(FillColor::Float([0.0; 4]).matches(ChannelType::UnormInt8));
+        assert!(FillColor::Float([0.0; 4]).matches(ChannelType::HalfFloat));
+        assert!(!FillColor::Float([0.0; 4]).matches(ChannelType::UnsignedInt8));
+        assert!(FillColor::Int([0; 4]).matches(ChannelType::SignedInt16));
+        assert!(!FillColor::Int([0; 4]).matches(ChannelType::UnsignedInt16));
+        assert!(FillColor::UInt([0; 4]).matches(ChannelType::UnsignedInt32));
+        assert!(!FillColor::UInt([0; 4]).matches(ChannelType::Float));
+    }
+
+    #[test]
+    fn image_limits() {
+        let limits = ImageLimits { max_2d: [8192, 4096], max_3d: [2048, 2048, 256], max_array_size: 64 };
+        assert!(limits.allows(MemObjectType::Image1D, [8192, 1, 1]));
+        assert!(!limits.allows(MemObjectType::Image1D, [8193, 1, 1]));
+        assert!(limits.allows(MemObjectType::Image1DArray, [1024, 64, 1]));
+        assert!(!limits.allows(MemObjectType::Image1DArray, [1024, 65, 1]));
+        assert!(limits.allows(MemObjectType::Image2D, [8192, 4096, 1]));
+        assert!(!limits.allows(MemObjectType::Image2D, [8192, 4097, 1]));
+        assert!(!limits.allows(MemObjectType::Image2DArray, [16, 16, 65]));
+        assert!(limits.allows(MemObjectType::Image3D, [2048, 2048, 256]));
+        assert!(!limits.allows(MemObjectType::Image3D, [2048, 2048, 257]));
+    }
+}
@@ -0,0 +1,81 @@
+use std::ffi::c_void;
+use crate::result::{Error, Result};
+
+/// Query a fixed size info value using the given `clGet*Info` style getter.
+///
+/// The getter receives the value size, the value pointer and the pointer for the returned size.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the type of the queried info.
+pub(crate) unsafe fn get_value<T, F>(getter: F) -> Result<T>
+where
+    F: Fn(usize, *mut c_void, *mut usize) -> i32,
+{
+    let mut value_size: usize = 0;
+    let result = Error::from(getter(0, std::ptr::null_mut(), &mut value_size));
+    if result != Error::Success {
+        return Err(result);
+    }
+    if value_size != std::mem::size_of::<T>() {
+        return Err(Error::InvalidValue);
+    }
+
+    let mut value = std::mem::MaybeUninit::<T>::uninit();
+    let result = Error::from(getter(value_size, value.as_mut_ptr() as *mut c_void, std::ptr::null_mut()));
+    if result != Error::Success {
+        return Err(result);
+    }
+
+    Ok(value.assume_init())
+}
+
+/// Query an array info value using the given `clGet*Info` style getter.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the element type of the queried info.
+pub(crate) unsafe fn get_vec<T: Copy, F>(getter: F) -> Result<Vec<T>>
+where
+    F: Fn(usize, *mut c_void, *mut usize) -> i32,
+{
+    let mut value_size: usize = 0;
+    let result = Error::from(getter(0, std::ptr::null_mut(), &mut value_size));
+    if result != Error::Success {
+        return Err(result);
+    }
+
+    let element_size = std::mem::size_of::<T>();
+    if !value_size.is_multiple_of(element_size) {
+        return Err(Error::InvalidValue);
+    }
+
+    let mut values: Vec<T> = Vec::with_capacity(value_size / element_size);
+    if value_size == 0 {
+        return Ok(values);
+    }
+
+    let result = Error::from(getter(value_size, values.as_mut_ptr() as *mut c_void, std::ptr::null_mut()));
+    if result != Error::Success {
+        return Err(result);
+    }
+    values.set_len(value_size / element_size);
+
+    Ok(values)
+}
+
+/// Query a string info value using the given `clGet*Info` style getter.
+///
+/// # Safety
+///
+/// The caller must make sure the queried info is actually a string.
+pub(crate) unsafe fn get_string<F>(getter: F) -> Result<String>
+where
+    F: Fn(usize, *mut c_void, *mut usize) -> i32,
+{
+    let mut value: Vec<u8> = get_vec(getter)?;
+    if value.last() == Some(&0) {
+        value.truncate(value.len() - 1);
+    }
+    Ok(String::from_utf8_lossy(value.as_slice()).into_owned())
+}
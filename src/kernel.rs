@@ -0,0 +1,927 @@
+use std::ffi::{c_void, CString};
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use crate::buffer::{Buffer, SubBuffer};
+use crate::device::cl_get_device_info;
+use crate::info;
+use crate::memory::{MemObject, Residency};
+use crate::native::{clCreateKernel, clCreateKernelsInProgram, clGetKernelArgInfo, clGetKernelInfo, clGetKernelSubGroupInfo, clGetKernelWorkGroupInfo, clReleaseKernel, clReleaseMemObject, clRetainMemObject, clSetKernelArg, clSetKernelArgSVMPointer, clSetKernelExecInfo};
+use crate::program::Program;
+use crate::queue::CommandQueue;
+use crate::result::{Error, Result};
+use crate::svm::SvmAllocation;
+use crate::types::{AccessQualifier, AddressQualifier, DeviceId, DeviceInfo, KernelArgInfo, KernelExecInfo, KernelId, KernelInfo, MemId, Pod, SubGroupInfo, TypeQualifier, WorkGroupInfo};
+
+/// The declaration of a kernel argument as reported by `clGetKernelArgInfo`.
+///
+/// This information is only available if the program was built with `-cl-kernel-arg-info`.
+#[derive(Clone, Debug)]
+pub struct ArgInfo {
+    name: String,
+    type_name: String,
+    address_qualifier: AddressQualifier,
+    access_qualifier: AccessQualifier,
+    type_qualifier: TypeQualifier,
+}
+
+impl ArgInfo {
+    /// The name of the argument.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The type name of the argument, e.g. `float4` or `int*`.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The address qualifier of the argument.
+    pub fn address_qualifier(&self) -> AddressQualifier {
+        self.address_qualifier
+    }
+
+    /// The access qualifier of the argument.
+    pub fn access_qualifier(&self) -> AccessQualifier {
+        self.access_qualifier
+    }
+
+    /// The type qualifiers of the argument.
+    pub fn type_qualifier(&self) -> TypeQualifier {
+        self.type_qualifier
+    }
+
+    /// Whether the argument is a pointer.
+    pub fn is_pointer(&self) -> bool {
+        self.type_name.ends_with('*')
+    }
+
+    /// Whether the (pointee) type of the argument is compatible with the given OpenCL C type name.
+    ///
+    /// Unknown type names (`None`) are compatible with every type, except for 3-component vectors.
+    /// These have the size of 4-component vectors, so e.g. `float3` requires a `float4` (`[f32; 4]`).
+    pub fn type_matches(&self, cl_type_name: Option<String>) -> bool {
+        let declared = normalize_type_name(self.type_name.trim_end_matches('*'));
+        if let Some(scalar) = declared.strip_suffix('3').filter(|scalar| VECTOR_SCALARS.contains(scalar)) {
+            return cl_type_name == Some(format!("{}4", scalar));
+        }
+        match cl_type_name {
+            Some(name) => declared == "void" || declared == name,
+            None => true,
+        }
+    }
+}
+
+impl Display for ArgInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.address_qualifier {
+            AddressQualifier::Private => write!(f, "{} {}", self.type_name, self.name),
+            qualifier => write!(f, "{} {} {}", qualifier, self.type_name, self.name),
+        }
+    }
+}
+
+/// The OpenCL C scalar types vector types are built from.
+const VECTOR_SCALARS: [&str; 11] = ["char", "uchar", "short", "ushort", "int", "uint", "long", "ulong", "half", "float", "double"];
+
+/// Normalize the spelling of an OpenCL C type name, e.g. `unsigned int` to `uint`.
+fn normalize_type_name(name: &str) -> String {
+    let name = name.trim();
+    match name.strip_prefix("unsigned ") {
+        Some(rest) => format!("u{}", rest.trim()),
+        None => name.to_string(),
+    }
+}
+
+/// A value which can be passed as a kernel argument.
+///
+/// # Safety
+///
+/// [KernelArg::value] must point to [KernelArg::size] readable bytes (or be null for `__local` arguments)
+/// which are valid to pass to `clSetKernelArg`.
+pub unsafe trait KernelArg {
+    /// The size of the argument value in bytes.
+    fn size(&self) -> usize;
+
+    /// Pointer to the argument value.
+    fn value(&self) -> *const c_void;
+
+    /// Whether the argument is compatible with the given declaration.
+    fn matches(&self, info: &ArgInfo) -> bool;
+
+    /// Human readable description of the argument, used for error messages.
+    fn describe(&self) -> String;
+
+    /// Whether the argument is a sampler, which counts against `DeviceInfo::MaxSamplers`.
+    fn is_sampler(&self) -> bool {
+        false
+    }
+
+    /// The shared virtual memory pointer to pass with `clSetKernelArgSVMPointer` instead of the value.
+    fn svm_pointer(&self) -> Option<*const c_void> {
+        None
+    }
+
+    /// The shared virtual memory passed as the argument, which the kernel keeps alive while it is bound
+    /// and records its launches in.
+    fn svm_allocation(&self) -> Option<&Arc<SvmAllocation>> {
+        None
+    }
+
+    /// The memory object passed as the argument, which the kernel retains while it is bound
+    /// and whose residency launches of the kernel update.
+    fn mem_object(&self) -> Option<&dyn MemObject> {
+        None
+    }
+}
+
+unsafe impl<T: Pod> KernelArg for T {
+    fn size(&self) -> usize {
+        std::mem::size_of::<T>()
+    }
+
+    fn value(&self) -> *const c_void {
+        self as *const T as *const c_void
+    }
+
+    fn matches(&self, info: &ArgInfo) -> bool {
+        info.address_qualifier == AddressQualifier::Private && !info.is_pointer() && info.type_matches(T::cl_type_name())
+    }
+
+    fn describe(&self) -> String {
+        match T::cl_type_name() {
+            Some(name) => format!("a value of type `{}`", name),
+            None => format!("a value of {} bytes", std::mem::size_of::<T>()),
+        }
+    }
+}
+
+unsafe impl<T: Pod> KernelArg for Buffer<T> {
+    fn size(&self) -> usize {
+        std::mem::size_of::<MemId>()
+    }
+
+    fn value(&self) -> *const c_void {
+        self.id_ref() as *const MemId as *const c_void
+    }
+
+    fn matches(&self, info: &ArgInfo) -> bool {
+        matches!(info.address_qualifier, AddressQualifier::Global | AddressQualifier::Constant)
+            && info.is_pointer()
+            && info.type_matches(T::cl_type_name())
+    }
+
+    fn describe(&self) -> String {
+        match T::cl_type_name() {
+            Some(name) => format!("a buffer of `{}`", name),
+            None => format!("a buffer of {} byte elements", std::mem::size_of::<T>()),
+        }
+    }
+
+    fn mem_object(&self) -> Option<&dyn MemObject> {
+        Some(self)
+    }
+}
+
+unsafe impl<T: Pod> KernelArg for SubBuffer<'_, T> {
+    fn size(&self) -> usize {
+        std::mem::size_of::<MemId>()
+    }
+
+    fn value(&self) -> *const c_void {
+        self.as_buffer().value()
+    }
+
+    fn matches(&self, info: &ArgInfo) -> bool {
+        self.as_buffer().matches(info)
+    }
+
+    fn describe(&self) -> String {
+        self.as_buffer().describe()
+    }
+
+    fn mem_object(&self) -> Option<&dyn MemObject> {
+        Some(self)
+    }
+}
+
+/// Size of a `__local` kernel argument, holding `len` elements of type `T`.
+#[derive(Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+pub struct Local<T: Pod> {
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod> Local<T> {
+    /// Local memory for `len` elements.
+    pub fn new(len: usize) -> Self {
+        Self { len, _marker: PhantomData }
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no local memory is requested.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+unsafe impl<T: Pod> KernelArg for Local<T> {
+    fn size(&self) -> usize {
+        self.len * std::mem::size_of::<T>()
+    }
+
+    fn value(&self) -> *const c_void {
+        std::ptr::null()
+    }
+
+    fn matches(&self, info: &ArgInfo) -> bool {
+        info.address_qualifier == AddressQualifier::Local && info.is_pointer() && info.type_matches(T::cl_type_name())
+    }
+
+    fn describe(&self) -> String {
+        format!("`__local` memory of {} bytes", self.size())
+    }
+}
+
+/// Error returned when setting a kernel argument failed.
+#[derive(Clone, Debug)]
+pub enum ArgError {
+    /// The OpenCL implementation rejected the argument.
+    Error(Error),
+
+    /// The argument does not match its declaration in the kernel source.
+    TypeMismatch {
+        /// The name of the kernel.
+        kernel: String,
+        /// The index of the argument.
+        index: u32,
+        /// The declaration of the argument.
+        info: ArgInfo,
+        /// Description of the value that was passed.
+        given: String,
+    },
+
+    /// More samplers were passed than `DeviceInfo::MaxSamplers` allows.
+    TooManySamplers {
+        /// The name of the kernel.
+        kernel: String,
+        /// The number of sampler arguments including the one being set.
+        count: usize,
+        /// The maximum number of samplers supported by the devices of the program.
+        max: u32,
+    },
+}
+
+impl From<Error> for ArgError {
+    fn from(error: Error) -> Self {
+        ArgError::Error(error)
+    }
+}
+
+impl Display for ArgError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgError::Error(error) => write!(f, "{}", error),
+            ArgError::TypeMismatch { kernel, index, info, given } => write!(
+                f, "Argument {} of kernel `{}` is declared as `{}`, but {} was passed.", index, kernel, info, given,
+            ),
+            ArgError::TooManySamplers { kernel, count, max } => write!(
+                f, "Kernel `{}` would use {} samplers, but the devices support at most {} (MaxSamplers).", kernel, count, max,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+/// Structure containing how a kernel can be launched on a device and how much memory it uses there.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct KernelWorkGroupInfo {
+    work_group_size: usize,
+    compile_work_group_size: Option<[usize; 3]>,
+    preferred_work_group_size_multiple: usize,
+    local_mem_size: u64,
+    private_mem_size: u64,
+    max_num_sub_groups: Option<usize>,
+    compile_num_sub_groups: Option<usize>,
+}
+
+impl KernelWorkGroupInfo {
+    /// Get the work-group information of a kernel for the given device.
+    ///
+    /// The sub-group information is only available on OpenCL 2.1 devices
+    /// and is skipped if the OpenCL library does not export `clGetKernelSubGroupInfo`.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidDevice` - The program of the kernel is not built for the device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::kernel::{Kernel, KernelWorkGroupInfo};
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let program = Program::from_source(&context, &["__kernel void noop() {}"])?;
+    /// program.build(&[], "")?;
+    /// let kernel = Kernel::new(&program, "noop")?;
+    ///
+    /// let info = KernelWorkGroupInfo::get(&kernel, context.devices()[0])?;
+    /// let local = info.work_group_size() / info.preferred_work_group_size_multiple() * info.preferred_work_group_size_multiple();
+    /// println!("Launching with {} work-items per group, {} bytes private memory each", local, info.private_mem_size());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(kernel: &Kernel, device: DeviceId) -> Result<Self> {
+        let compile_work_group_size: [usize; 3] = unsafe { kernel.work_group_info(device, WorkGroupInfo::CompileWorkGroupSize) }?;
+        // without clGetKernelSubGroupInfo the queries fail before calling into OpenCL and the fields are skipped
+        let sub_group_info = |name: SubGroupInfo| unsafe { kernel.sub_group_info::<usize>(device, name, &[]) }.ok();
+        Ok(Self {
+            work_group_size: unsafe { kernel.work_group_info(device, WorkGroupInfo::WorkGroupSize) }?,
+            compile_work_group_size: if compile_work_group_size == [0; 3] { None } else { Some(compile_work_group_size) },
+            preferred_work_group_size_multiple: unsafe { kernel.work_group_info(device, WorkGroupInfo::PreferredWorkGroupSizeMultiple) }?,
+            local_mem_size: unsafe { kernel.work_group_info(device, WorkGroupInfo::LocalMemSize) }?,
+            private_mem_size: unsafe { kernel.work_group_info(device, WorkGroupInfo::PrivateMemSize) }?,
+            max_num_sub_groups: sub_group_info(SubGroupInfo::MaxNumSubGroups),
+            compile_num_sub_groups: sub_group_info(SubGroupInfo::CompileNumSubGroups).filter(|&count| count != 0),
+        })
+    }
+
+    /// The maximum work-group size the kernel can be launched with,
+    /// which may be smaller than `DeviceInfo::MaxWorkGroupSize` due to register pressure.
+    pub fn work_group_size(&self) -> usize {
+        self.work_group_size
+    }
+
+    /// The work-group size required by `__attribute__((reqd_work_group_size(X, Y, Z)))`, if specified.
+    pub fn compile_work_group_size(&self) -> Option<[usize; 3]> {
+        self.compile_work_group_size
+    }
+
+    /// The work-group size should be a multiple of this for best performance, e.g. the SIMD width.
+    pub fn preferred_work_group_size_multiple(&self) -> usize {
+        self.preferred_work_group_size_multiple
+    }
+
+    /// The local memory used by the kernel in bytes, including `__local` arguments set at the time of the query.
+    pub fn local_mem_size(&self) -> u64 {
+        self.local_mem_size
+    }
+
+    /// The minimum private memory used by each work-item in bytes.
+    pub fn private_mem_size(&self) -> u64 {
+        self.private_mem_size
+    }
+
+    /// The maximum number of sub-groups in a work-group, `None` if sub-groups are not supported.
+    pub fn max_num_sub_groups(&self) -> Option<usize> {
+        self.max_num_sub_groups
+    }
+
+    /// The number of sub-groups required by the kernel source, if specified.
+    pub fn compile_num_sub_groups(&self) -> Option<usize> {
+        self.compile_num_sub_groups
+    }
+}
+
+/// An OpenCL kernel.
+///
+/// The kernel is released when dropped.
+pub struct Kernel {
+    id: KernelId,
+    program: Program,
+    name: String,
+    num_args: u32,
+    args: Option<Vec<ArgInfo>>,
+    samplers: Vec<u32>,
+    memory_args: Vec<MemoryArg>,
+}
+
+/// The memory passed as a kernel argument, kept alive by the kernel and updated when it is launched.
+struct MemoryArg {
+    index: u32,
+    mem_object: Option<RetainedMem>,
+    svm_allocation: Option<Arc<SvmAllocation>>,
+}
+
+/// A memory object bound to a kernel argument, retained until the argument is replaced or the kernel is dropped,
+/// as `clSetKernelArg` does not retain it.
+struct RetainedMem {
+    id: MemId,
+    residency: Option<Residency>,
+}
+
+impl RetainedMem {
+    fn new(object: &dyn MemObject) -> Self {
+        let id = object.mem_id();
+        let result = unsafe { clRetainMemObject(id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id, residency: object.residency().cloned() }
+    }
+}
+
+impl Drop for RetainedMem {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseMemObject(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+impl Kernel {
+    /// Create the kernel with the given name from a built program.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidProgramExecutable` - The program has not been built successfully.
+    /// * `Error::InvalidKernelName` - The program contains no kernel with the given name.
+    /// * `Error::InvalidKernelDefinition` - The kernel is defined differently for the devices of the program.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::kernel::Kernel;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::{DeviceType, MemFlags};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let program = Program::from_source(&context, &["__kernel void scale(__global float *a, float b) {}"])?;
+    /// program.build(&[], "-cl-kernel-arg-info")?;
+    ///
+    /// let buffer = Buffer::<f32>::new(&context, MemFlags::READ_WRITE, 1024)?;
+    /// let mut kernel = Kernel::new(&program, "scale")?;
+    /// kernel.set_arg(0, &buffer)?;
+    /// kernel.set_arg(1, &2.0f32)?;
+    /// assert!(kernel.set_arg(1, &2i32).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(program: &Program, name: &str) -> Result<Self> {
+        let name = CString::new(name).map_err(|_| Error::InvalidValue)?;
+        let mut result: i32 = 0;
+        let id = unsafe { clCreateKernel(program.id(), name.as_ptr(), &mut result) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Self::from_id(id, program)
+    }
+
+    /// Create all kernels of a built program.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidProgramExecutable` - The program has not been built successfully.
+    /// * `Error::InvalidKernelDefinition` - A kernel is defined differently for the devices of the program.
+    pub fn all(program: &Program) -> Result<Vec<Self>> {
+        let mut num_kernels: u32 = 0;
+        let result = unsafe { clCreateKernelsInProgram(program.id(), 0, std::ptr::null_mut(), &mut num_kernels) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        let mut ids: Vec<KernelId> = vec![0; num_kernels as usize];
+        if num_kernels == 0 {
+            return Ok(vec![]);
+        }
+
+        let result = unsafe { clCreateKernelsInProgram(program.id(), num_kernels, ids.as_mut_ptr(), std::ptr::null_mut()) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        // take ownership of all kernels first, so none of them leaks if querying one fails
+        let kernels: Vec<Self> = ids.into_iter().map(|id| Self::unqueried(id, program)).collect();
+        kernels.into_iter().map(Self::query).collect()
+    }
+
+    fn unqueried(id: KernelId, program: &Program) -> Self {
+        Self { id, program: program.clone(), name: String::new(), num_args: 0, args: None, samplers: vec![], memory_args: vec![] }
+    }
+
+    fn from_id(id: KernelId, program: &Program) -> Result<Self> {
+        Self::unqueried(id, program).query()
+    }
+
+    fn query(mut self) -> Result<Self> {
+        let id = self.id;
+        self.name = unsafe { info::get_string(|size, value, size_ret| clGetKernelInfo(id, KernelInfo::FunctionName, size, value, size_ret)) }?;
+        self.num_args = unsafe { info::get_value(|size, value, size_ret| clGetKernelInfo(id, KernelInfo::NumArgs, size, value, size_ret)) }?;
+        self.args = (0..self.num_args).map(|index| self.query_arg_info(index)).collect::<Result<Vec<_>>>().ok();
+        Ok(self)
+    }
+
+    fn query_arg_info(&self, index: u32) -> Result<ArgInfo> {
+        let getter = |name: KernelArgInfo| {
+            let id = self.id;
+            move |size, value, size_ret| unsafe { clGetKernelArgInfo(id, index, name, size, value, size_ret) }
+        };
+
+        let address_qualifier: u32 = unsafe { info::get_value(getter(KernelArgInfo::AddressQualifier)) }?;
+        let access_qualifier: u32 = unsafe { info::get_value(getter(KernelArgInfo::AccessQualifier)) }?;
+        let type_qualifier: u64 = unsafe { info::get_value(getter(KernelArgInfo::TypeQualifier)) }?;
+        Ok(ArgInfo {
+            name: unsafe { info::get_string(getter(KernelArgInfo::Name)) }?,
+            type_name: unsafe { info::get_string(getter(KernelArgInfo::TypeName)) }?,
+            address_qualifier: AddressQualifier::try_from(address_qualifier).map_err(|_| Error::InvalidValue)?,
+            access_qualifier: AccessQualifier::try_from(access_qualifier).map_err(|_| Error::InvalidValue)?,
+            type_qualifier: TypeQualifier::from_raw(type_qualifier),
+        })
+    }
+
+    /// The native handle of the kernel.
+    pub fn id(&self) -> KernelId {
+        self.id
+    }
+
+    /// The program the kernel was created from.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// The function name of the kernel.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of arguments of the kernel.
+    pub fn num_args(&self) -> u32 {
+        self.num_args
+    }
+
+    /// The declarations of the kernel arguments.
+    ///
+    /// Returns `None` if the program was not built with `-cl-kernel-arg-info`.
+    pub fn arg_info(&self) -> Option<&[ArgInfo]> {
+        self.args.as_deref()
+    }
+
+    /// Set the argument with the given index.
+    ///
+    /// If the declarations of the kernel arguments are available,
+    /// the argument is checked against its declaration before it is passed to OpenCL.
+    /// Memory objects are retained by the kernel until the argument is replaced or the kernel is dropped.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `ArgError::TypeMismatch` - The argument does not match its declaration.
+    /// * `ArgError::TooManySamplers` - The kernel would use more samplers than `DeviceInfo::MaxSamplers` allows.
+    /// * `Error::InvalidArgIndex` - The index is out of range.
+    /// * `Error::InvalidArgValue` - The value is not valid for the argument.
+    /// * `Error::InvalidArgSize` - The size of the value does not match the declaration.
+    /// * `Error::InvalidOperation` - Shared virtual memory was passed, but the OpenCL library does not support it.
+    pub fn set_arg<A: KernelArg + ?Sized>(&mut self, index: u32, arg: &A) -> std::result::Result<(), ArgError> {
+        if index >= self.num_args {
+            return Err(ArgError::Error(Error::InvalidArgIndex));
+        }
+
+        if let Some(info) = self.args.as_ref().map(|args| &args[index as usize]) {
+            if !arg.matches(info) {
+                return Err(ArgError::TypeMismatch {
+                    kernel: self.name.clone(),
+                    index,
+                    info: info.clone(),
+                    given: arg.describe(),
+                });
+            }
+        }
+
+        let is_new_sampler = arg.is_sampler() && !self.samplers.contains(&index);
+        if is_new_sampler {
+            let max = self.max_samplers()?;
+            if self.samplers.len() as u32 >= max {
+                return Err(ArgError::TooManySamplers { kernel: self.name.clone(), count: self.samplers.len() + 1, max });
+            }
+        }
+
+        let result = match arg.svm_pointer() {
+            Some(pointer) => {
+                let set_arg_svm_pointer = clSetKernelArgSVMPointer().ok_or(Error::InvalidOperation)?;
+                unsafe { set_arg_svm_pointer(self.id, index, pointer) }
+            }
+            None => unsafe { clSetKernelArg(self.id, index, arg.size(), arg.value()) },
+        };
+        if result != 0 {
+            return Err(ArgError::Error(Error::from(result)));
+        }
+
+        self.memory_args.retain(|memory| memory.index != index);
+        let (mem_object, svm_allocation) = (arg.mem_object().map(RetainedMem::new), arg.svm_allocation().cloned());
+        if mem_object.is_some() || svm_allocation.is_some() {
+            self.memory_args.push(MemoryArg { index, mem_object, svm_allocation });
+        }
+
+        if is_new_sampler {
+            self.samplers.push(index);
+        } else if !arg.is_sampler() {
+            self.samplers.retain(|&sampler| sampler != index);
+        }
+        Ok(())
+    }
+
+    /// Record a launch of the kernel on the given queue in the memory bound to its arguments.
+    pub(crate) fn used_by(&self, queue: &CommandQueue) {
+        for memory in &self.memory_args {
+            if let Some(residency) = memory.mem_object.as_ref().and_then(|object| object.residency.as_ref()) {
+                residency.used_by(queue);
+            }
+            if let Some(allocation) = &memory.svm_allocation {
+                allocation.used_by(queue);
+            }
+        }
+    }
+
+    /// Declare shared virtual memory which the kernel accesses through pointers stored in other allocations,
+    /// e.g. the nodes of a linked graph, rather than through its arguments.
+    ///
+    /// Unlike allocations passed with [Kernel::set_arg], launches of the kernel are not tracked for these allocations,
+    /// so they must not be dropped before all launches using them have completed.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidOperation` - No device of the program supports shared virtual memory.
+    pub fn set_svm_pointers(&mut self, pointers: &[*const c_void]) -> Result<()> {
+        let set_exec_info = clSetKernelExecInfo().ok_or(Error::InvalidOperation)?;
+        let result = unsafe {
+            set_exec_info(self.id, KernelExecInfo::SvmPtrs, std::mem::size_of_val(pointers), pointers.as_ptr() as *const c_void)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+
+    /// Set whether the kernel may access any host allocation, which requires `SvmCapabilities::FINE_GRAIN_SYSTEM`.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidOperation` - Enabled, but no device of the program supports fine-grained system allocations.
+    pub fn set_fine_grain_system(&mut self, enabled: bool) -> Result<()> {
+        let set_exec_info = clSetKernelExecInfo().ok_or(Error::InvalidOperation)?;
+        let value = enabled as u32;
+        let result = unsafe {
+            set_exec_info(self.id, KernelExecInfo::SvmFineGrainSystem, std::mem::size_of::<u32>(), &value as *const u32 as *const c_void)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+
+    /// The maximum sub-group size when launching the kernel with the given local work size (OpenCL 2.1).
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The local work size does not have 1 to 3 dimensions.
+    /// * `Error::InvalidDevice` - The program of the kernel is not built for the device.
+    /// * `Error::InvalidOperation` - The device or the OpenCL library does not support sub-groups.
+    pub fn max_sub_group_size(&self, device: DeviceId, local: &[usize]) -> Result<usize> {
+        check_dimensions(local.len())?;
+        unsafe { self.sub_group_info(device, SubGroupInfo::MaxSubGroupSizeForNdRange, local) }
+    }
+
+    /// The number of sub-groups per work-group when launching the kernel with the given local work size (OpenCL 2.1).
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The local work size does not have 1 to 3 dimensions.
+    /// * `Error::InvalidDevice` - The program of the kernel is not built for the device.
+    /// * `Error::InvalidOperation` - The device or the OpenCL library does not support sub-groups.
+    pub fn sub_group_count(&self, device: DeviceId, local: &[usize]) -> Result<usize> {
+        check_dimensions(local.len())?;
+        unsafe { self.sub_group_info(device, SubGroupInfo::SubGroupCountForNdRange, local) }
+    }
+
+    /// The local work size with the given number of dimensions which yields `count` sub-groups per work-group (OpenCL 2.1).
+    ///
+    /// Returns a local work size of zeros if no such size exists.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The number of dimensions is not 1 to 3.
+    /// * `Error::InvalidDevice` - The program of the kernel is not built for the device.
+    /// * `Error::InvalidOperation` - The device or the OpenCL library does not support sub-groups.
+    pub fn local_size_for_sub_group_count(&self, device: DeviceId, count: usize, dimensions: usize) -> Result<Vec<usize>> {
+        check_dimensions(dimensions)?;
+        let get_sub_group_info = clGetKernelSubGroupInfo().ok_or(Error::InvalidOperation)?;
+        let mut local = vec![0usize; dimensions];
+        let result = unsafe {
+            get_sub_group_info(
+                self.id, device, SubGroupInfo::LocalSizeForSubGroupCount, std::mem::size_of::<usize>(), &count as *const usize as *const c_void,
+                std::mem::size_of_val(local.as_slice()), local.as_mut_ptr() as *mut c_void, std::ptr::null_mut(),
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(local)
+    }
+
+    /// Get a work-group info of the kernel for the given device.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure the generic type parameter matches the type of the work-group info.
+    unsafe fn work_group_info<T>(&self, device: DeviceId, name: WorkGroupInfo) -> Result<T> {
+        info::get_value(|size, value, size_ret| clGetKernelWorkGroupInfo(self.id, device, name, size, value, size_ret))
+    }
+
+    /// Get a sub-group info of the kernel for the given device and input value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure the generic type parameter matches the type of the sub-group info.
+    unsafe fn sub_group_info<T>(&self, device: DeviceId, name: SubGroupInfo, input: &[usize]) -> Result<T> {
+        let get_sub_group_info = clGetKernelSubGroupInfo().ok_or(Error::InvalidOperation)?;
+        let input_ptr = if input.is_empty() { std::ptr::null() } else { input.as_ptr() as *const c_void };
+        info::get_value(|size, value, size_ret| {
+            get_sub_group_info(self.id, device, name, std::mem::size_of_val(input), input_ptr, size, value, size_ret)
+        })
+    }
+
+    /// The number of samplers all devices of the program support in a kernel.
+    fn max_samplers(&self) -> Result<u32> {
+        let mut max = u32::MAX;
+        for device in self.program.devices()? {
+            max = max.min(unsafe { cl_get_device_info::<u32>(device, DeviceInfo::MaxSamplers) }?);
+        }
+        Ok(max)
+    }
+}
+
+/// Check that a local work size has 1 to 3 dimensions, as required by the sub-group queries.
+fn check_dimensions(dimensions: usize) -> Result<()> {
+    if !(1..=3).contains(&dimensions) {
+        return Err(Error::InvalidValue);
+    }
+    Ok(())
+}
+
+impl Drop for Kernel {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseKernel(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Context;
+    use crate::platform::cl_get_platform_ids;
+    use crate::types::{DeviceType, MemFlags};
+
+    fn arg(address_qualifier: AddressQualifier, type_name: &str) -> ArgInfo {
+        ArgInfo {
+            name: "x".to_string(),
+            type_name: type_name.to_string(),
+            address_qualifier,
+            access_qualifier: AccessQualifier::None,
+            type_qualifier: TypeQualifier::NONE,
+        }
+    }
+
+    #[test]
+    fn scalar_args() {
+        assert!(1.0f32.matches(&arg(AddressQualifier::Private, "float")));
+        assert!(1u32.matches(&arg(AddressQualifier::Private, "unsigned int")));
+        assert!([1.0f32; 4].matches(&arg(AddressQualifier::Private, "float4")));
+        assert!(!1i32.matches(&arg(AddressQualifier::Private, "float")));
+        assert!(!1.0f32.matches(&arg(AddressQualifier::Global, "float*")));
+        assert!(![1.0f32; 4].matches(&arg(AddressQualifier::Private, "float")));
+        assert!(![1.0f32; 3].matches(&arg(AddressQualifier::Private, "float3")));
+        assert!([1.0f32; 4].matches(&arg(AddressQualifier::Private, "float3")));
+        assert!(!1.0f32.matches(&arg(AddressQualifier::Private, "float3")));
+        assert!(1usize.matches(&arg(AddressQualifier::Private, "vec3")));
+    }
+
+    #[test]
+    fn local_args() {
+        let local = Local::<f32>::new(16);
+        assert_eq!(local.size(), 64);
+        assert!(local.value().is_null());
+        assert!(local.matches(&arg(AddressQualifier::Local, "float*")));
+        assert!(local.matches(&arg(AddressQualifier::Local, "void*")));
+        assert!(!local.matches(&arg(AddressQualifier::Local, "int*")));
+        assert!(!local.matches(&arg(AddressQualifier::Global, "float*")));
+    }
+
+    #[test]
+    fn type_mismatch_display() {
+        let error = ArgError::TypeMismatch {
+            kernel: "scale".to_string(),
+            index: 1,
+            info: arg(AddressQualifier::Private, "float"),
+            given: 2i32.describe(),
+        };
+        assert_eq!(error.to_string(), "Argument 1 of kernel `scale` is declared as `float x`, but a value of type `int` was passed.");
+    }
+
+    #[test]
+    fn sub_group_dimensions() {
+        assert_eq!(check_dimensions(0), Err(Error::InvalidValue));
+        assert_eq!(check_dimensions(1), Ok(()));
+        assert_eq!(check_dimensions(3), Ok(()));
+        assert_eq!(check_dimensions(4), Err(Error::InvalidValue));
+    }
+
+    #[test]
+    fn work_group_infos() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let program = Program::from_source(&context, &[
+                "__kernel __attribute__((reqd_work_group_size(8, 4, 1))) void fixed(__global float *a) { a[get_global_id(0)] = 0; }",
+                "__kernel void flexible(__global float *a, __local float *b) { b[0] = a[0]; }",
+            ]).unwrap();
+            program.build(&[], "").unwrap();
+            let fixed = Kernel::new(&program, "fixed").unwrap();
+            let mut flexible = Kernel::new(&program, "flexible").unwrap();
+            flexible.set_arg(1, &Local::<f32>::new(256)).unwrap();
+
+            for &device in context.devices() {
+                let info = KernelWorkGroupInfo::get(&fixed, device).unwrap();
+                assert_eq!(info.compile_work_group_size(), Some([8, 4, 1]));
+                assert!(info.work_group_size() > 0);
+                assert!(info.preferred_work_group_size_multiple() > 0);
+
+                let info = KernelWorkGroupInfo::get(&flexible, device).unwrap();
+                assert_eq!(info.compile_work_group_size(), None);
+                assert!(info.local_mem_size() >= 1024);
+                if info.max_num_sub_groups().is_some() {
+                    assert!(flexible.sub_group_count(device, &[info.work_group_size()]).unwrap() > 0);
+                }
+                assert_eq!(flexible.max_sub_group_size(device, &[]), Err(Error::InvalidValue));
+            }
+        }
+    }
+
+    #[test]
+    fn retain_memory_args() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let program = Program::from_source(&context, &["__kernel void zero(__global float *a) { a[get_global_id(0)] = 0; }"]).unwrap();
+            program.build(&[], "").unwrap();
+            let mut kernel = Kernel::new(&program, "zero").unwrap();
+            let first = Buffer::<f32>::new(&context, MemFlags::READ_WRITE, 16).unwrap();
+            let second = Buffer::<f32>::new(&context, MemFlags::READ_WRITE, 16).unwrap();
+
+            kernel.set_arg(0, &first).unwrap();
+            assert_eq!(first.reference_count().unwrap(), 2);
+            kernel.set_arg(0, &second).unwrap();
+            assert_eq!(first.reference_count().unwrap(), 1);
+            assert_eq!(second.reference_count().unwrap(), 2);
+            drop(kernel);
+            assert_eq!(second.reference_count().unwrap(), 1);
+
+            let mut kernel = Kernel::new(&program, "zero").unwrap();
+            let slice = first.slice(..16).unwrap();
+            assert_eq!(KernelArg::size(&slice), std::mem::size_of::<MemId>());
+            kernel.set_arg(0, &slice).unwrap();
+        }
+    }
+
+    #[test]
+    fn too_many_samplers_display() {
+        let error = ArgError::TooManySamplers { kernel: "blur".to_string(), count: 17, max: 16 };
+        assert_eq!(error.to_string(), "Kernel `blur` would use 17 samplers, but the devices support at most 16 (MaxSamplers).");
+    }
+}
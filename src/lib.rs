@@ -4,6 +4,9 @@
 /// Module containing all the native function declarations.
 pub(crate) mod native;
 
+/// Helpers for the various `clGet*Info` functions.
+pub(crate) mod info;
+
 /// Results and error codes.
 pub mod result;
 
@@ -15,3 +18,36 @@ pub mod platform;
 
 /// Device related functions and types.
 pub mod device;
+
+/// Context related functions and types.
+pub mod context;
+
+/// Command queue related functions and types.
+pub mod queue;
+
+/// Memory object related functions and types.
+pub mod memory;
+
+/// Buffer related functions and types.
+pub mod buffer;
+
+/// Program related functions and types.
+pub mod program;
+
+/// Kernel related functions and types.
+pub mod kernel;
+
+/// Event related functions and types.
+pub mod event;
+
+/// Image related functions and types.
+pub mod image;
+
+/// Sampler related functions and types.
+pub mod sampler;
+
+/// Shared virtual memory related functions and types.
+pub mod svm;
+
+/// Pipe related functions and types.
+pub mod pipe;
@@ -0,0 +1,317 @@
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use crate::event::Event;
+use crate::info;
+use crate::native::{clEnqueueUnmapMemObject, clGetMemObjectInfo, clSetMemObjectDestructorCallback};
+use crate::queue::CommandQueue;
+use crate::result::{Error, Result};
+use crate::types::{DeviceId, EventId, MapFlags, MemFlags, MemId, MemInfo, Pod};
+
+/// Get a memory object info for the given memory object.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the type of the memory object info.
+pub unsafe fn cl_get_mem_object_info<T: Sized>(memobj: MemId, name: MemInfo) -> Result<T> {
+    info::get_value(|size, value: *mut c_void, size_ret| clGetMemObjectInfo(memobj, name, size, value, size_ret))
+}
+
+type DestructorCallback = Box<dyn FnOnce() + Send>;
+
+extern "C" fn destructor_callback(_memobj: MemId, user_data: *mut c_void) {
+    let callback = unsafe { Box::from_raw(user_data as *mut DestructorCallback) };
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(callback));
+}
+
+/// Common interface of all OpenCL memory objects (buffers, images, pipes).
+pub trait MemObject {
+    /// The native handle of the memory object.
+    fn mem_id(&self) -> MemId;
+
+    /// The flags specified when the memory object was created.
+    fn mem_flags(&self) -> Result<MemFlags> {
+        let raw: u64 = unsafe { cl_get_mem_object_info(self.mem_id(), MemInfo::Flags) }?;
+        Ok(MemFlags::from_raw(raw))
+    }
+
+    /// The actual size of the data store associated with the memory object in bytes.
+    fn mem_size(&self) -> Result<usize> {
+        unsafe { cl_get_mem_object_info(self.mem_id(), MemInfo::Size) }
+    }
+
+    /// The current reference count of the memory object.
+    ///
+    /// # Remarks
+    ///
+    /// The reference count should be considered immediately stale.
+    /// It is only useful for identifying memory leaks.
+    fn reference_count(&self) -> Result<u32> {
+        unsafe { cl_get_mem_object_info(self.mem_id(), MemInfo::ReferenceCount) }
+    }
+
+    /// Register a callback which is called once the memory object has been deleted,
+    /// i.e. when the last handle to it has been released and no command uses it anymore.
+    ///
+    /// This is useful to free host memory used by the memory object. Callbacks are called
+    /// in the reverse order of their registration, possibly from a thread of the OpenCL implementation.
+    /// The callback is kept alive by the memory object until then.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidMemObject` - The memory object is invalid.
+    fn set_destructor_callback<F>(&self, callback: F) -> Result<()>
+    where
+        Self: Sized,
+        F: FnOnce() + Send + 'static,
+    {
+        let callback: *mut DestructorCallback = Box::into_raw(Box::new(Box::new(callback)));
+        let result = unsafe { clSetMemObjectDestructorCallback(self.mem_id(), Some(destructor_callback), callback as *mut c_void) };
+        if result != 0 {
+            drop(unsafe { Box::from_raw(callback) });
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+
+    /// The residency tracker of the memory object, `None` if its location is not tracked.
+    fn residency(&self) -> Option<&Residency> {
+        None
+    }
+}
+
+/// Where the contents of a memory object were last known to reside.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+pub enum Location {
+    /// The memory object has not been used by a tracked command yet.
+    #[default]
+    Unknown,
+    /// The memory object was migrated to the host.
+    Host,
+    /// The memory object was migrated to or used by a command on the device.
+    Device(DeviceId),
+}
+
+/// The last known location of a memory object, shared by all handles to it.
+///
+/// The location is updated by migrations, by the transfer commands of the memory object and by launches
+/// of kernels it was passed to with [Kernel::set_arg](crate::kernel::Kernel::set_arg). Sub-buffers share the tracker of their parent.
+/// OpenCL may still migrate the memory object implicitly, so the location should only be used as a hint,
+/// e.g. to decide which memory objects to prefetch.
+#[derive(Clone, Default)]
+pub struct Residency {
+    location: Arc<Mutex<Location>>,
+}
+
+impl Residency {
+    /// The last known location of the memory object.
+    pub fn location(&self) -> Location {
+        *self.location.lock().unwrap()
+    }
+
+    /// Record that the memory object was moved to the given location.
+    pub(crate) fn set(&self, location: Location) {
+        *self.location.lock().unwrap() = location;
+    }
+
+    /// Record that a command on the given queue used the memory object.
+    pub(crate) fn used_by(&self, queue: &CommandQueue) {
+        self.set(Location::Device(queue.device()));
+    }
+}
+
+/// The regions of a memory object which are currently mapped into host memory, shared by all handles to it.
+///
+/// Regions are given as `[x, y, z]` origin and extent, in bytes within the parent buffer for buffers and
+/// sub-buffers and in pixels for images. Sub-buffers share the tracker of their parent.
+#[derive(Clone, Default)]
+pub(crate) struct MappedRegions {
+    regions: Arc<Mutex<Vec<Region>>>,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+struct Region {
+    origin: [usize; 3],
+    extent: [usize; 3],
+    writable: bool,
+}
+
+impl Region {
+    fn overlaps(&self, other: &Region) -> bool {
+        (0..3).all(|i| self.origin[i] < other.origin[i] + other.extent[i] && other.origin[i] < self.origin[i] + self.extent[i])
+    }
+}
+
+impl MappedRegions {
+    /// Record that a region is about to be mapped, until the returned lock is dropped.
+    ///
+    /// Fails with `Error::InvalidOperation` if an overlapping region is mapped and either of them is writable,
+    /// as the host would otherwise get aliasing mutable access to the memory.
+    pub(crate) fn lock(&self, origin: [usize; 3], extent: [usize; 3], writable: bool) -> Result<RegionLock> {
+        let region = Region { origin, extent, writable };
+        let mut regions = self.regions.lock().unwrap();
+        if regions.iter().any(|mapped| (mapped.writable || writable) && mapped.overlaps(&region)) {
+            return Err(Error::InvalidOperation);
+        }
+        regions.push(region);
+        Ok(RegionLock { regions: self.clone(), region })
+    }
+}
+
+/// A region recorded in [MappedRegions], removed again when dropped.
+pub(crate) struct RegionLock {
+    regions: MappedRegions,
+    region: Region,
+}
+
+impl Drop for RegionLock {
+    fn drop(&mut self) {
+        let mut regions = self.regions.regions.lock().unwrap();
+        if let Some(index) = regions.iter().position(|mapped| *mapped == self.region) {
+            regions.swap_remove(index);
+        }
+    }
+}
+
+/// Check that map flags request write access, which is required for mutable access to the mapped region.
+pub(crate) fn check_write_flags(flags: MapFlags) -> Result<()> {
+    if !flags.contains(MapFlags::WRITE) && !flags.contains(MapFlags::WRITE_INVALIDATE_REGION) {
+        return Err(Error::InvalidValue);
+    }
+    Ok(())
+}
+
+/// A region of a buffer or image mapped with a blocking `clEnqueueMap*` call, unmapped when dropped.
+pub(crate) struct Mapping<'a, T: Pod> {
+    mem: MemId,
+    queue: &'a CommandQueue,
+    ptr: *mut T,
+    len: usize,
+    row_pitch: usize,
+    slice_pitch: usize,
+    unmapped: bool,
+    _lock: RegionLock,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: Pod> Mapping<'a, T> {
+    /// Wrap a mapped region.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` mapped elements of the given memory object, which stay valid until unmapped,
+    /// and `lock` must cover the mapped region.
+    pub(crate) unsafe fn new(
+        mem: MemId, queue: &'a CommandQueue, ptr: *mut T, len: usize, row_pitch: usize, slice_pitch: usize, lock: RegionLock,
+    ) -> Self {
+        Self { mem, queue, ptr, len, row_pitch, slice_pitch, unmapped: false, _lock: lock, _marker: PhantomData }
+    }
+
+    fn unmap(mut self) -> Result<Event> {
+        self.unmapped = true;
+        self.enqueue_unmap()
+    }
+
+    fn enqueue_unmap(&self) -> Result<Event> {
+        let mut event: EventId = 0;
+        let result = unsafe { clEnqueueUnmapMemObject(self.queue.id(), self.mem, self.ptr as *mut c_void, 0, std::ptr::null(), &mut event) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(Event::from_id(event))
+    }
+
+    fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<T: Pod> Drop for Mapping<'_, T> {
+    fn drop(&mut self) {
+        if self.unmapped {
+            return;
+        }
+        if let Ok(event) = self.enqueue_unmap() {
+            let result = event.wait();
+            debug_assert!(result.is_ok());
+        }
+    }
+}
+
+/// Defines a guard giving host access to a mapped region of a buffer or image.
+macro_rules! map_guard {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        pub struct $name<'a, T: Pod> {
+            mapping: Mapping<'a, T>,
+        }
+
+        impl<'a, T: Pod> $name<'a, T> {
+            pub(crate) fn new(mapping: Mapping<'a, T>) -> Self {
+                Self { mapping }
+            }
+
+            /// The distance between the starts of two rows in elements, zero for buffers.
+            pub fn row_pitch(&self) -> usize {
+                self.mapping.row_pitch
+            }
+
+            /// The distance between the starts of two slices or array images in elements, zero for buffers and 1D or 2D images.
+            pub fn slice_pitch(&self) -> usize {
+                self.mapping.slice_pitch
+            }
+
+            /// Unmap the region, returning the event of the unmap command.
+            ///
+            /// Dropping the guard unmaps the region as well, but waits for the unmap to complete and ignores errors.
+            ///
+            /// # Errors
+            ///
+            /// The following errors may be returned:
+            ///
+            /// * `Error::OutOfResources` - The unmap could not be enqueued.
+            pub fn unmap(self) -> Result<Event> {
+                self.mapping.unmap()
+            }
+        }
+
+        impl<T: Pod> Deref for $name<'_, T> {
+            type Target = [T];
+
+            fn deref(&self) -> &Self::Target {
+                self.mapping.as_slice()
+            }
+        }
+    };
+}
+
+map_guard!(
+    /// Host access to a region of a buffer or image mapped for writing, unmapped when dropped.
+    ///
+    /// The guard mutably borrows the handle it was mapped through, so that handle can neither be used by other
+    /// commands nor dropped while it is mapped. No overlapping region of the memory object can be mapped
+    /// through any other handle, clone or sub-buffer until the guard is dropped or unmapped.
+    MapGuard
+);
+
+map_guard!(
+    /// Read-only host access to a mapped region of a buffer or image, unmapped when dropped.
+    ///
+    /// The guard mutably borrows the handle it was mapped through, so that handle can neither be used by other
+    /// commands nor dropped while it is mapped. Overlapping regions of the memory object can only be mapped
+    /// for reading through other handles, clones or sub-buffers until the guard is dropped or unmapped.
+    MapReadGuard
+);
+
+impl<T: Pod> DerefMut for MapGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.mapping.as_mut_slice()
+    }
+}
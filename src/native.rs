@@ -1,7 +1,114 @@
 #![allow(non_snake_case)]
 
-use std::ffi::c_void;
-use crate::types::{DeviceId, DeviceInfo, PlatformId, PlatformInfo};
+use std::ffi::{c_char, c_void};
+use std::sync::OnceLock;
+use crate::types::{CommandQueueId, CommandQueueInfo, ContextId, ContextInfo, ContextProperty, DeviceId, DeviceInfo, EventId, EventInfo, ImageInfo, KernelArgInfo, KernelExecInfo, KernelId, KernelInfo, MemId, MemInfo, PartitionProperty, PipeInfo, PlatformId, PlatformInfo, ProfilingInfo, ProgramBuildInfo, ProgramId, ProgramInfo, QueueProperty, SamplerId, SamplerInfo, SamplerProperty, SubGroupInfo, WorkGroupInfo};
+
+/// cl_image_format with unchecked channel order and channel data type.
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+pub struct RawImageFormat {
+    pub channel_order: u32,
+    pub channel_data_type: u32,
+}
+
+/// cl_buffer_region
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+pub struct BufferRegion {
+    pub origin: usize,
+    pub size: usize,
+}
+
+/// cl_name_version
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct NameVersion {
+    pub version: u32,
+    pub name: [c_char; 64],
+}
+
+/// cl_image_desc
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+pub struct ImageDesc {
+    pub image_type: u32,
+    pub image_width: usize,
+    pub image_height: usize,
+    pub image_depth: usize,
+    pub image_array_size: usize,
+    pub image_row_pitch: usize,
+    pub image_slice_pitch: usize,
+    pub num_mip_levels: u32,
+    pub num_samples: u32,
+    pub mem_object: MemId,
+}
+
+/// void (CL_CALLBACK *pfn_notify)(const char *errinfo, const void *private_info, size_t cb, void *user_data)
+pub type ContextNotify = extern "C" fn(errinfo: *const c_char, private_info: *const c_void, cb: usize, user_data: *mut c_void);
+
+/// void (CL_CALLBACK *pfn_notify)(cl_mem memobj, void *user_data)
+pub type MemObjectDestructorNotify = extern "C" fn(memobj: MemId, user_data: *mut c_void);
+
+/// void (CL_CALLBACK *pfn_notify)(cl_program program, void *user_data)
+pub type ProgramNotify = extern "C" fn(program: ProgramId, user_data: *mut c_void);
+
+/// void (CL_CALLBACK *pfn_notify)(cl_event event, cl_int event_command_status, void *user_data)
+pub type EventNotify = extern "C" fn(event: EventId, event_command_status: i32, user_data: *mut c_void);
+
+/// cl_program (CL_API_CALL *clCreateProgramWithILKHR_fn)(cl_context context, const void *il, size_t length, cl_int *errcode_ret)
+pub type CreateProgramWithILKHR = unsafe extern "C" fn(context: ContextId, il: *const c_void, length: usize, errcode_ret: *mut i32) -> ProgramId;
+
+/// Look up the address of an exported OpenCL function, `0` if the loaded library does not export it.
+///
+/// `name` must be nul-terminated.
+#[cfg(unix)]
+fn symbol_address(name: &str) -> usize {
+    extern "C" {
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    }
+
+    // RTLD_DEFAULT, i.e. search all libraries loaded into the process.
+    let handle = if cfg!(target_os = "macos") { -2isize as *mut c_void } else { std::ptr::null_mut() };
+    unsafe { dlsym(handle, name.as_ptr() as *const c_char) as usize }
+}
+
+/// Look up the address of an exported OpenCL function, `0` if the loaded library does not export it.
+///
+/// `name` must be nul-terminated.
+#[cfg(windows)]
+fn symbol_address(name: &str) -> usize {
+    extern "system" {
+        fn GetModuleHandleA(module_name: *const c_char) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, proc_name: *const c_char) -> *mut c_void;
+    }
+
+    let module = unsafe { GetModuleHandleA(c"OpenCL.dll".as_ptr()) };
+    if module.is_null() {
+        return 0;
+    }
+    unsafe { GetProcAddress(module, name.as_ptr() as *const c_char) as usize }
+}
+
+/// Declares OpenCL functions introduced after OpenCL 1.2.
+///
+/// They are resolved at runtime instead of being linked, so that the crate still links against OpenCL 1.2 libraries.
+/// Each declaration becomes a function returning the entry point, or `None` if the library does not export it.
+macro_rules! runtime_fns {
+    ($($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name() -> Option<unsafe extern "C" fn($($arg: $ty),*) $(-> $ret)?> {
+                static ADDRESS: OnceLock<usize> = OnceLock::new();
+                let address = *ADDRESS.get_or_init(|| symbol_address(concat!(stringify!($name), "\0")));
+                if address == 0 {
+                    return None;
+                }
+                Some(unsafe { std::mem::transmute::<usize, unsafe extern "C" fn($($ty),*) $(-> $ret)?>(address) })
+            }
+        )*
+    };
+}
 
 extern "C" {
     /// cl_int clGetPlatformIDs(cl_uint num_entries, cl_platform_id *platforms, cl_uint *num_platforms)
@@ -15,4 +122,258 @@ extern "C" {
 
     /// cl_int clGetDeviceInfo(cl_device_id device, cl_device_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
     pub fn clGetDeviceInfo(device: DeviceId, name: DeviceInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clCreateSubDevices(cl_device_id in_device, const cl_device_partition_property *properties, cl_uint num_devices, cl_device_id *out_devices, cl_uint *num_devices_ret)
+    pub fn clCreateSubDevices(in_device: DeviceId, properties: *const PartitionProperty, num_devices: u32, out_devices: *mut DeviceId, num_devices_ret: *mut u32) -> i32;
+
+    /// cl_int clRetainDevice(cl_device_id device)
+    pub fn clRetainDevice(device: DeviceId) -> i32;
+
+    /// cl_int clReleaseDevice(cl_device_id device)
+    pub fn clReleaseDevice(device: DeviceId) -> i32;
+
+    /// cl_context clCreateContext(const cl_context_properties *properties, cl_uint num_devices, const cl_device_id *devices, void (CL_CALLBACK *pfn_notify)(...), void *user_data, cl_int *errcode_ret)
+    pub fn clCreateContext(properties: *const ContextProperty, num_devices: u32, devices: *const DeviceId, pfn_notify: Option<ContextNotify>, user_data: *mut c_void, errcode_ret: *mut i32) -> ContextId;
+
+    /// cl_context clCreateContextFromType(const cl_context_properties *properties, cl_device_type device_type, void (CL_CALLBACK *pfn_notify)(...), void *user_data, cl_int *errcode_ret)
+    pub fn clCreateContextFromType(properties: *const ContextProperty, device_type: u64, pfn_notify: Option<ContextNotify>, user_data: *mut c_void, errcode_ret: *mut i32) -> ContextId;
+
+    /// cl_int clRetainContext(cl_context context)
+    pub fn clRetainContext(context: ContextId) -> i32;
+
+    /// cl_int clReleaseContext(cl_context context)
+    pub fn clReleaseContext(context: ContextId) -> i32;
+
+    /// cl_int clGetContextInfo(cl_context context, cl_context_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetContextInfo(context: ContextId, name: ContextInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_command_queue clCreateCommandQueue(cl_context context, cl_device_id device, cl_command_queue_properties properties, cl_int *errcode_ret)
+    pub fn clCreateCommandQueue(context: ContextId, device: DeviceId, properties: u64, errcode_ret: *mut i32) -> CommandQueueId;
+
+    /// cl_int clRetainCommandQueue(cl_command_queue command_queue)
+    pub fn clRetainCommandQueue(command_queue: CommandQueueId) -> i32;
+
+    /// cl_int clReleaseCommandQueue(cl_command_queue command_queue)
+    pub fn clReleaseCommandQueue(command_queue: CommandQueueId) -> i32;
+
+    /// cl_int clGetCommandQueueInfo(cl_command_queue command_queue, cl_command_queue_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetCommandQueueInfo(command_queue: CommandQueueId, name: CommandQueueInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clFlush(cl_command_queue command_queue)
+    pub fn clFlush(command_queue: CommandQueueId) -> i32;
+
+    /// cl_int clFinish(cl_command_queue command_queue)
+    pub fn clFinish(command_queue: CommandQueueId) -> i32;
+
+    /// cl_int clEnqueueMarkerWithWaitList(cl_command_queue command_queue, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueMarkerWithWaitList(command_queue: CommandQueueId, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueBarrierWithWaitList(cl_command_queue command_queue, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueBarrierWithWaitList(command_queue: CommandQueueId, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_mem clCreateBuffer(cl_context context, cl_mem_flags flags, size_t size, void *host_ptr, cl_int *errcode_ret)
+    pub fn clCreateBuffer(context: ContextId, flags: u64, size: usize, host_ptr: *mut c_void, errcode_ret: *mut i32) -> MemId;
+
+    /// cl_mem clCreateSubBuffer(cl_mem buffer, cl_mem_flags flags, cl_buffer_create_type buffer_create_type, const void *buffer_create_info, cl_int *errcode_ret)
+    pub fn clCreateSubBuffer(buffer: MemId, flags: u64, buffer_create_type: u32, buffer_create_info: *const c_void, errcode_ret: *mut i32) -> MemId;
+
+    /// cl_int clRetainMemObject(cl_mem memobj)
+    pub fn clRetainMemObject(memobj: MemId) -> i32;
+
+    /// cl_int clReleaseMemObject(cl_mem memobj)
+    pub fn clReleaseMemObject(memobj: MemId) -> i32;
+
+    /// cl_int clSetMemObjectDestructorCallback(cl_mem memobj, void (CL_CALLBACK *pfn_notify)(cl_mem memobj, void *user_data), void *user_data)
+    pub fn clSetMemObjectDestructorCallback(memobj: MemId, pfn_notify: Option<MemObjectDestructorNotify>, user_data: *mut c_void) -> i32;
+
+    /// cl_int clGetMemObjectInfo(cl_mem memobj, cl_mem_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetMemObjectInfo(memobj: MemId, name: MemInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clEnqueueReadBuffer(cl_command_queue command_queue, cl_mem buffer, cl_bool blocking_read, size_t offset, size_t size, void *ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueReadBuffer(command_queue: CommandQueueId, buffer: MemId, blocking_read: u32, offset: usize, size: usize, ptr: *mut c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueWriteBuffer(cl_command_queue command_queue, cl_mem buffer, cl_bool blocking_write, size_t offset, size_t size, const void *ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueWriteBuffer(command_queue: CommandQueueId, buffer: MemId, blocking_write: u32, offset: usize, size: usize, ptr: *const c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueCopyBuffer(cl_command_queue command_queue, cl_mem src_buffer, cl_mem dst_buffer, size_t src_offset, size_t dst_offset, size_t size, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueCopyBuffer(command_queue: CommandQueueId, src_buffer: MemId, dst_buffer: MemId, src_offset: usize, dst_offset: usize, size: usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueReadBufferRect(cl_command_queue command_queue, cl_mem buffer, cl_bool blocking_read, const size_t *buffer_origin, const size_t *host_origin, const size_t *region, size_t buffer_row_pitch, size_t buffer_slice_pitch, size_t host_row_pitch, size_t host_slice_pitch, void *ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueReadBufferRect(command_queue: CommandQueueId, buffer: MemId, blocking_read: u32, buffer_origin: *const usize, host_origin: *const usize, region: *const usize, buffer_row_pitch: usize, buffer_slice_pitch: usize, host_row_pitch: usize, host_slice_pitch: usize, ptr: *mut c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueWriteBufferRect(cl_command_queue command_queue, cl_mem buffer, cl_bool blocking_write, const size_t *buffer_origin, const size_t *host_origin, const size_t *region, size_t buffer_row_pitch, size_t buffer_slice_pitch, size_t host_row_pitch, size_t host_slice_pitch, const void *ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueWriteBufferRect(command_queue: CommandQueueId, buffer: MemId, blocking_write: u32, buffer_origin: *const usize, host_origin: *const usize, region: *const usize, buffer_row_pitch: usize, buffer_slice_pitch: usize, host_row_pitch: usize, host_slice_pitch: usize, ptr: *const c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueCopyBufferRect(cl_command_queue command_queue, cl_mem src_buffer, cl_mem dst_buffer, const size_t *src_origin, const size_t *dst_origin, const size_t *region, size_t src_row_pitch, size_t src_slice_pitch, size_t dst_row_pitch, size_t dst_slice_pitch, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueCopyBufferRect(command_queue: CommandQueueId, src_buffer: MemId, dst_buffer: MemId, src_origin: *const usize, dst_origin: *const usize, region: *const usize, src_row_pitch: usize, src_slice_pitch: usize, dst_row_pitch: usize, dst_slice_pitch: usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_program clCreateProgramWithSource(cl_context context, cl_uint count, const char **strings, const size_t *lengths, cl_int *errcode_ret)
+    pub fn clCreateProgramWithSource(context: ContextId, count: u32, strings: *const *const c_char, lengths: *const usize, errcode_ret: *mut i32) -> ProgramId;
+
+    /// cl_program clCreateProgramWithBinary(cl_context context, cl_uint num_devices, const cl_device_id *device_list, const size_t *lengths, const unsigned char **binaries, cl_int *binary_status, cl_int *errcode_ret)
+    pub fn clCreateProgramWithBinary(context: ContextId, num_devices: u32, device_list: *const DeviceId, lengths: *const usize, binaries: *const *const u8, binary_status: *mut i32, errcode_ret: *mut i32) -> ProgramId;
+
+    /// cl_program clCreateProgramWithBuiltInKernels(cl_context context, cl_uint num_devices, const cl_device_id *device_list, const char *kernel_names, cl_int *errcode_ret)
+    pub fn clCreateProgramWithBuiltInKernels(context: ContextId, num_devices: u32, device_list: *const DeviceId, kernel_names: *const c_char, errcode_ret: *mut i32) -> ProgramId;
+
+    /// cl_int clBuildProgram(cl_program program, cl_uint num_devices, const cl_device_id *device_list, const char *options, void (CL_CALLBACK *pfn_notify)(...), void *user_data)
+    pub fn clBuildProgram(program: ProgramId, num_devices: u32, device_list: *const DeviceId, options: *const c_char, pfn_notify: Option<ProgramNotify>, user_data: *mut c_void) -> i32;
+
+    /// cl_int clRetainProgram(cl_program program)
+    pub fn clRetainProgram(program: ProgramId) -> i32;
+
+    /// cl_int clReleaseProgram(cl_program program)
+    pub fn clReleaseProgram(program: ProgramId) -> i32;
+
+    /// cl_int clCompileProgram(cl_program program, cl_uint num_devices, const cl_device_id *device_list, const char *options, cl_uint num_input_headers, const cl_program *input_headers, const char **header_include_names, void (CL_CALLBACK *pfn_notify)(...), void *user_data)
+    pub fn clCompileProgram(program: ProgramId, num_devices: u32, device_list: *const DeviceId, options: *const c_char, num_input_headers: u32, input_headers: *const ProgramId, header_include_names: *const *const c_char, pfn_notify: Option<ProgramNotify>, user_data: *mut c_void) -> i32;
+
+    /// cl_program clLinkProgram(cl_context context, cl_uint num_devices, const cl_device_id *device_list, const char *options, cl_uint num_input_programs, const cl_program *input_programs, void (CL_CALLBACK *pfn_notify)(...), void *user_data, cl_int *errcode_ret)
+    pub fn clLinkProgram(context: ContextId, num_devices: u32, device_list: *const DeviceId, options: *const c_char, num_input_programs: u32, input_programs: *const ProgramId, pfn_notify: Option<ProgramNotify>, user_data: *mut c_void, errcode_ret: *mut i32) -> ProgramId;
+
+    /// cl_int clGetProgramInfo(cl_program program, cl_program_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetProgramInfo(program: ProgramId, name: ProgramInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clGetProgramBuildInfo(cl_program program, cl_device_id device, cl_program_build_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetProgramBuildInfo(program: ProgramId, device: DeviceId, name: ProgramBuildInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_kernel clCreateKernel(cl_program program, const char *kernel_name, cl_int *errcode_ret)
+    pub fn clCreateKernel(program: ProgramId, kernel_name: *const c_char, errcode_ret: *mut i32) -> KernelId;
+
+    /// cl_int clCreateKernelsInProgram(cl_program program, cl_uint num_kernels, cl_kernel *kernels, cl_uint *num_kernels_ret)
+    pub fn clCreateKernelsInProgram(program: ProgramId, num_kernels: u32, kernels: *mut KernelId, num_kernels_ret: *mut u32) -> i32;
+
+    /// cl_int clReleaseKernel(cl_kernel kernel)
+    pub fn clReleaseKernel(kernel: KernelId) -> i32;
+
+    /// cl_int clSetKernelArg(cl_kernel kernel, cl_uint arg_index, size_t arg_size, const void *arg_value)
+    pub fn clSetKernelArg(kernel: KernelId, arg_index: u32, arg_size: usize, arg_value: *const c_void) -> i32;
+
+    /// cl_int clGetKernelInfo(cl_kernel kernel, cl_kernel_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetKernelInfo(kernel: KernelId, name: KernelInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clGetKernelArgInfo(cl_kernel kernel, cl_uint arg_indx, cl_kernel_arg_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetKernelArgInfo(kernel: KernelId, arg_index: u32, name: KernelArgInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clGetKernelWorkGroupInfo(cl_kernel kernel, cl_device_id device, cl_kernel_work_group_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetKernelWorkGroupInfo(kernel: KernelId, device: DeviceId, name: WorkGroupInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clEnqueueNDRangeKernel(cl_command_queue command_queue, cl_kernel kernel, cl_uint work_dim, const size_t *global_work_offset, const size_t *global_work_size, const size_t *local_work_size, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueNDRangeKernel(command_queue: CommandQueueId, kernel: KernelId, work_dim: u32, global_work_offset: *const usize, global_work_size: *const usize, local_work_size: *const usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clWaitForEvents(cl_uint num_events, const cl_event *event_list)
+    pub fn clWaitForEvents(num_events: u32, event_list: *const EventId) -> i32;
+
+    /// cl_int clGetEventInfo(cl_event event, cl_event_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetEventInfo(event: EventId, name: EventInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clRetainEvent(cl_event event)
+    pub fn clRetainEvent(event: EventId) -> i32;
+
+    /// cl_int clReleaseEvent(cl_event event)
+    pub fn clReleaseEvent(event: EventId) -> i32;
+
+    /// cl_int clSetEventCallback(cl_event event, cl_int command_exec_callback_type, void (CL_CALLBACK *pfn_notify)(...), void *user_data)
+    pub fn clSetEventCallback(event: EventId, command_exec_callback_type: i32, pfn_notify: Option<EventNotify>, user_data: *mut c_void) -> i32;
+
+    /// cl_int clGetEventProfilingInfo(cl_event event, cl_profiling_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetEventProfilingInfo(event: EventId, name: ProfilingInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_event clCreateUserEvent(cl_context context, cl_int *errcode_ret)
+    pub fn clCreateUserEvent(context: ContextId, errcode_ret: *mut i32) -> EventId;
+
+    /// cl_int clSetUserEventStatus(cl_event event, cl_int execution_status)
+    pub fn clSetUserEventStatus(event: EventId, execution_status: i32) -> i32;
+
+    /// cl_mem clCreateImage(cl_context context, cl_mem_flags flags, const cl_image_format *image_format, const cl_image_desc *image_desc, void *host_ptr, cl_int *errcode_ret)
+    pub fn clCreateImage(context: ContextId, flags: u64, image_format: *const RawImageFormat, image_desc: *const ImageDesc, host_ptr: *mut c_void, errcode_ret: *mut i32) -> MemId;
+
+    /// cl_int clGetSupportedImageFormats(cl_context context, cl_mem_flags flags, cl_mem_object_type image_type, cl_uint num_entries, cl_image_format *image_formats, cl_uint *num_image_formats)
+    pub fn clGetSupportedImageFormats(context: ContextId, flags: u64, image_type: u32, num_entries: u32, image_formats: *mut RawImageFormat, num_image_formats: *mut u32) -> i32;
+
+    /// cl_int clGetImageInfo(cl_mem image, cl_image_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetImageInfo(image: MemId, name: ImageInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clEnqueueReadImage(cl_command_queue command_queue, cl_mem image, cl_bool blocking_read, const size_t *origin, const size_t *region, size_t row_pitch, size_t slice_pitch, void *ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueReadImage(command_queue: CommandQueueId, image: MemId, blocking_read: u32, origin: *const usize, region: *const usize, row_pitch: usize, slice_pitch: usize, ptr: *mut c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueWriteImage(cl_command_queue command_queue, cl_mem image, cl_bool blocking_write, const size_t *origin, const size_t *region, size_t input_row_pitch, size_t input_slice_pitch, const void *ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueWriteImage(command_queue: CommandQueueId, image: MemId, blocking_write: u32, origin: *const usize, region: *const usize, input_row_pitch: usize, input_slice_pitch: usize, ptr: *const c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_sampler clCreateSampler(cl_context context, cl_bool normalized_coords, cl_addressing_mode addressing_mode, cl_filter_mode filter_mode, cl_int *errcode_ret)
+    pub fn clCreateSampler(context: ContextId, normalized_coords: u32, addressing_mode: u32, filter_mode: u32, errcode_ret: *mut i32) -> SamplerId;
+
+    /// cl_int clRetainSampler(cl_sampler sampler)
+    pub fn clRetainSampler(sampler: SamplerId) -> i32;
+
+    /// cl_int clReleaseSampler(cl_sampler sampler)
+    pub fn clReleaseSampler(sampler: SamplerId) -> i32;
+
+    /// cl_int clGetSamplerInfo(cl_sampler sampler, cl_sampler_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetSamplerInfo(sampler: SamplerId, name: SamplerInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// void *clGetExtensionFunctionAddressForPlatform(cl_platform_id platform, const char *func_name)
+    pub fn clGetExtensionFunctionAddressForPlatform(platform: PlatformId, func_name: *const c_char) -> *mut c_void;
+
+    /// void *clEnqueueMapBuffer(cl_command_queue command_queue, cl_mem buffer, cl_bool blocking_map, cl_map_flags map_flags, size_t offset, size_t size, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event, cl_int *errcode_ret)
+    pub fn clEnqueueMapBuffer(command_queue: CommandQueueId, buffer: MemId, blocking_map: u32, map_flags: u64, offset: usize, size: usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId, errcode_ret: *mut i32) -> *mut c_void;
+
+    /// void *clEnqueueMapImage(cl_command_queue command_queue, cl_mem image, cl_bool blocking_map, cl_map_flags map_flags, const size_t *origin, const size_t *region, size_t *image_row_pitch, size_t *image_slice_pitch, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event, cl_int *errcode_ret)
+    pub fn clEnqueueMapImage(command_queue: CommandQueueId, image: MemId, blocking_map: u32, map_flags: u64, origin: *const usize, region: *const usize, image_row_pitch: *mut usize, image_slice_pitch: *mut usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId, errcode_ret: *mut i32) -> *mut c_void;
+
+    /// cl_int clEnqueueUnmapMemObject(cl_command_queue command_queue, cl_mem memobj, void *mapped_ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueUnmapMemObject(command_queue: CommandQueueId, memobj: MemId, mapped_ptr: *mut c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueFillBuffer(cl_command_queue command_queue, cl_mem buffer, const void *pattern, size_t pattern_size, size_t offset, size_t size, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueFillBuffer(command_queue: CommandQueueId, buffer: MemId, pattern: *const c_void, pattern_size: usize, offset: usize, size: usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueFillImage(cl_command_queue command_queue, cl_mem image, const void *fill_color, const size_t *origin, const size_t *region, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueFillImage(command_queue: CommandQueueId, image: MemId, fill_color: *const c_void, origin: *const usize, region: *const usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueMigrateMemObjects(cl_command_queue command_queue, cl_uint num_mem_objects, const cl_mem *mem_objects, cl_mem_migration_flags flags, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueMigrateMemObjects(command_queue: CommandQueueId, num_mem_objects: u32, mem_objects: *const MemId, flags: u64, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+}
+
+runtime_fns! {
+    /// cl_command_queue clCreateCommandQueueWithProperties(cl_context context, cl_device_id device, const cl_queue_properties *properties, cl_int *errcode_ret)
+    pub fn clCreateCommandQueueWithProperties(context: ContextId, device: DeviceId, properties: *const QueueProperty, errcode_ret: *mut i32) -> CommandQueueId;
+
+    /// cl_sampler clCreateSamplerWithProperties(cl_context context, const cl_sampler_properties *sampler_properties, cl_int *errcode_ret)
+    pub fn clCreateSamplerWithProperties(context: ContextId, sampler_properties: *const SamplerProperty, errcode_ret: *mut i32) -> SamplerId;
+
+    /// cl_program clCreateProgramWithIL(cl_context context, const void *il, size_t length, cl_int *errcode_ret)
+    pub fn clCreateProgramWithIL(context: ContextId, il: *const c_void, length: usize, errcode_ret: *mut i32) -> ProgramId;
+
+    /// void *clSVMAlloc(cl_context context, cl_svm_mem_flags flags, size_t size, cl_uint alignment)
+    pub fn clSVMAlloc(context: ContextId, flags: u64, size: usize, alignment: u32) -> *mut c_void;
+
+    /// void clSVMFree(cl_context context, void *svm_pointer)
+    pub fn clSVMFree(context: ContextId, svm_pointer: *mut c_void);
+
+    /// cl_int clSetKernelArgSVMPointer(cl_kernel kernel, cl_uint arg_index, const void *arg_value)
+    pub fn clSetKernelArgSVMPointer(kernel: KernelId, arg_index: u32, arg_value: *const c_void) -> i32;
+
+    /// cl_int clSetKernelExecInfo(cl_kernel kernel, cl_kernel_exec_info param_name, size_t param_value_size, const void *param_value)
+    pub fn clSetKernelExecInfo(kernel: KernelId, name: KernelExecInfo, value_size: usize, value: *const c_void) -> i32;
+
+    /// cl_int clEnqueueSVMMap(cl_command_queue command_queue, cl_bool blocking_map, cl_map_flags flags, void *svm_ptr, size_t size, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueSVMMap(command_queue: CommandQueueId, blocking_map: u32, flags: u64, svm_ptr: *mut c_void, size: usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueSVMUnmap(cl_command_queue command_queue, void *svm_ptr, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueSVMUnmap(command_queue: CommandQueueId, svm_ptr: *mut c_void, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueSVMMemcpy(cl_command_queue command_queue, cl_bool blocking_copy, void *dst_ptr, const void *src_ptr, size_t size, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueSVMMemcpy(command_queue: CommandQueueId, blocking_copy: u32, dst_ptr: *mut c_void, src_ptr: *const c_void, size: usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_int clEnqueueSVMMemFill(cl_command_queue command_queue, void *svm_ptr, const void *pattern, size_t pattern_size, size_t size, cl_uint num_events_in_wait_list, const cl_event *event_wait_list, cl_event *event)
+    pub fn clEnqueueSVMMemFill(command_queue: CommandQueueId, svm_ptr: *mut c_void, pattern: *const c_void, pattern_size: usize, size: usize, num_events_in_wait_list: u32, event_wait_list: *const EventId, event: *mut EventId) -> i32;
+
+    /// cl_mem clCreatePipe(cl_context context, cl_mem_flags flags, cl_uint pipe_packet_size, cl_uint pipe_max_packets, const cl_pipe_properties *properties, cl_int *errcode_ret)
+    pub fn clCreatePipe(context: ContextId, flags: u64, pipe_packet_size: u32, pipe_max_packets: u32, properties: *const isize, errcode_ret: *mut i32) -> MemId;
+
+    /// cl_int clGetPipeInfo(cl_mem pipe, cl_pipe_info param_name, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetPipeInfo(pipe: MemId, name: PipeInfo, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
+
+    /// cl_int clGetKernelSubGroupInfo(cl_kernel kernel, cl_device_id device, cl_kernel_sub_group_info param_name, size_t input_value_size, const void *input_value, size_t param_value_size, void *param_value, size_t *param_value_size_ret)
+    pub fn clGetKernelSubGroupInfo(kernel: KernelId, device: DeviceId, name: SubGroupInfo, input_value_size: usize, input_value: *const c_void, value_size: usize, value: *mut c_void, value_size_ret: *mut usize) -> i32;
 }
@@ -0,0 +1,208 @@
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use crate::context::Context;
+use crate::device::cl_get_device_info;
+use crate::info;
+use crate::kernel::{ArgInfo, KernelArg};
+use crate::memory::MemObject;
+use crate::native::{clCreatePipe, clGetPipeInfo, clReleaseMemObject, clRetainMemObject};
+use crate::platform::Platform;
+use crate::result::{Error, Result};
+use crate::types::{AddressQualifier, DeviceInfo, MemFlags, MemId, PipeInfo, Pod, TypeQualifier};
+
+/// Get a pipe info for the given pipe.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the type of the pipe info.
+pub unsafe fn cl_get_pipe_info<T: Sized>(pipe: MemId, name: PipeInfo) -> Result<T> {
+    let get_pipe_info = clGetPipeInfo().ok_or(Error::InvalidOperation)?;
+    info::get_value(|size, value, size_ret| get_pipe_info(pipe, name, size, value, size_ret))
+}
+
+/// Check the packet size and count of a pipe against the maximum packet size of the devices.
+fn check_packets(packet_size: usize, max_packet_size: u32, max_packets: u32) -> Result<()> {
+    if packet_size == 0 || packet_size > max_packet_size as usize || max_packets == 0 {
+        return Err(Error::InvalidPipeSize);
+    }
+    Ok(())
+}
+
+/// A typed OpenCL pipe transporting packets of type `T` between kernels (OpenCL 2.0).
+///
+/// Pipes can only be accessed by kernels, typically a producer writing and a consumer reading packets.
+///
+/// The pipe is retained when cloned and released when dropped.
+pub struct Pipe<T: Pod> {
+    id: MemId,
+    context: Context,
+    max_packets: u32,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod> Pipe<T> {
+    /// Create a pipe holding up to `max_packets` packets.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the pipe is created in.
+    /// * `flags` - Either `MemFlags::NONE`, `READ_WRITE` or `HOST_NO_ACCESS`.
+    /// * `max_packets` - The maximum number of packets the pipe can hold.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain anything besides `READ_WRITE` and `HOST_NO_ACCESS`.
+    /// * `Error::InvalidOperation` - The platform or the OpenCL library does not support OpenCL 2.0.
+    /// * `Error::InvalidPipeSize` - `max_packets` is zero or `T` is larger than `DeviceInfo::PipeMaxPacketSize` of a device.
+    /// * `Error::MemObjectAllocationFailure` - The memory for the pipe could not be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::pipe::Pipe;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::types::{DeviceType, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let pipe = Pipe::<[f32; 4]>::new(&context, MemFlags::HOST_NO_ACCESS, 1024)?;
+    /// assert_eq!(pipe.packet_size()?, 16);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(context: &Context, flags: MemFlags, max_packets: u32) -> Result<Self> {
+        if flags.raw() & !(MemFlags::READ_WRITE | MemFlags::HOST_NO_ACCESS).raw() != 0 {
+            return Err(Error::InvalidValue);
+        }
+        if Platform::get(context.platform())?.version().major() < 2 {
+            return Err(Error::InvalidOperation);
+        }
+        let create_pipe = clCreatePipe().ok_or(Error::InvalidOperation)?;
+
+        let mut max_packet_size = u32::MAX;
+        for &device in context.devices() {
+            max_packet_size = max_packet_size.min(unsafe { cl_get_device_info::<u32>(device, DeviceInfo::PipeMaxPacketSize) }?);
+        }
+        check_packets(std::mem::size_of::<T>(), max_packet_size, max_packets)?;
+
+        let mut result: i32 = 0;
+        let id = unsafe {
+            create_pipe(context.id(), flags.raw(), std::mem::size_of::<T>() as u32, max_packets, std::ptr::null(), &mut result)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone(), max_packets, _marker: PhantomData })
+    }
+
+    /// The native memory object id.
+    pub fn id(&self) -> MemId {
+        self.id
+    }
+
+    /// The context the pipe was created in.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// The maximum number of packets the pipe can hold, as specified on creation.
+    pub fn max_packets(&self) -> u32 {
+        self.max_packets
+    }
+
+    /// The size of a packet in bytes as reported by the OpenCL implementation.
+    pub fn packet_size(&self) -> Result<u32> {
+        unsafe { cl_get_pipe_info(self.id, PipeInfo::PacketSize) }
+    }
+}
+
+impl<T: Pod> MemObject for Pipe<T> {
+    fn mem_id(&self) -> MemId {
+        self.id
+    }
+}
+
+unsafe impl<T: Pod> KernelArg for Pipe<T> {
+    fn size(&self) -> usize {
+        std::mem::size_of::<MemId>()
+    }
+
+    fn value(&self) -> *const c_void {
+        &self.id as *const MemId as *const c_void
+    }
+
+    fn matches(&self, info: &ArgInfo) -> bool {
+        info.address_qualifier() == AddressQualifier::Global
+            && info.type_qualifier().contains(TypeQualifier::PIPE)
+            && info.type_matches(T::cl_type_name())
+    }
+
+    fn describe(&self) -> String {
+        match T::cl_type_name() {
+            Some(name) => format!("a pipe of `{}`", name),
+            None => format!("a pipe of {} byte packets", std::mem::size_of::<T>()),
+        }
+    }
+
+    fn mem_object(&self) -> Option<&dyn MemObject> {
+        Some(self)
+    }
+}
+
+impl<T: Pod> Clone for Pipe<T> {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainMemObject(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id: self.id, context: self.context.clone(), max_packets: self.max_packets, _marker: PhantomData }
+    }
+}
+
+impl<T: Pod> Drop for Pipe<T> {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseMemObject(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::cl_get_mem_object_info;
+    use crate::platform::cl_get_platform_ids;
+    use crate::types::{DeviceType, MemInfo, MemObjectType};
+
+    #[test]
+    fn packet_limits() {
+        assert!(check_packets(16, 1024, 1).is_ok());
+        assert!(check_packets(1024, 1024, 64).is_ok());
+        assert_eq!(check_packets(1025, 1024, 64), Err(Error::InvalidPipeSize));
+        assert_eq!(check_packets(16, 1024, 0), Err(Error::InvalidPipeSize));
+    }
+
+    #[test]
+    fn create_pipes() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let pipe = match Pipe::<[f32; 4]>::new(&context, MemFlags::HOST_NO_ACCESS, 64) {
+                Ok(pipe) => pipe,
+                Err(Error::InvalidOperation) => continue,
+                Err(error) => panic!("{}", error),
+            };
+            assert_eq!(pipe.packet_size().unwrap(), 16);
+            assert_eq!(unsafe { cl_get_pipe_info::<u32>(pipe.id(), PipeInfo::MaxPackets) }.unwrap(), 64);
+            let mem_type: u32 = unsafe { cl_get_mem_object_info(pipe.id(), MemInfo::Type) }.unwrap();
+            assert_eq!(mem_type, MemObjectType::Pipe as u32);
+
+            assert_eq!(Pipe::<u32>::new(&context, MemFlags::READ_ONLY, 64).err(), Some(Error::InvalidValue));
+            assert_eq!(Pipe::<u32>::new(&context, MemFlags::READ_WRITE, 0).err(), Some(Error::InvalidPipeSize));
+        }
+    }
+}
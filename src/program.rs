@@ -0,0 +1,752 @@
+use std::ffi::{c_char, c_void, CString};
+use std::fmt::{Display, Formatter};
+use crate::context::Context;
+use crate::device::{cl_get_device_built_in_kernels, cl_get_device_ils, cl_get_device_info};
+use crate::info;
+use crate::native::{clBuildProgram, clCompileProgram, clCreateProgramWithBinary, clCreateProgramWithBuiltInKernels, clCreateProgramWithIL, clCreateProgramWithSource, clGetExtensionFunctionAddressForPlatform, CreateProgramWithILKHR, clGetProgramBuildInfo, clGetProgramInfo, clLinkProgram, clReleaseProgram, clRetainProgram};
+use crate::platform::Platform;
+use crate::result::{Error, Result};
+use crate::types::{BuildStatus, DeviceId, DeviceInfo, ProgramBuildInfo, ProgramId, ProgramInfo};
+
+/// The outcome of building a program for a single device.
+#[derive(Clone, Debug)]
+pub struct BuildLog {
+    device: DeviceId,
+    status: BuildStatus,
+    log: String,
+}
+
+impl BuildLog {
+    /// The device the program was built for.
+    pub fn device(&self) -> DeviceId {
+        self.device
+    }
+
+    /// The build status for the device.
+    pub fn status(&self) -> BuildStatus {
+        self.status
+    }
+
+    /// The build log (compiler output) for the device.
+    pub fn log(&self) -> &str {
+        &self.log
+    }
+}
+
+/// Error returned when building a program failed.
+///
+/// Besides the error code it carries the build status and log of every device the program was built for.
+#[derive(Clone, Debug)]
+pub struct BuildError {
+    error: Error,
+    logs: Vec<BuildLog>,
+}
+
+impl BuildError {
+    /// The error code returned by the OpenCL implementation.
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// The build status and log of each device.
+    pub fn logs(&self) -> &[BuildLog] {
+        &self.logs
+    }
+}
+
+impl From<Error> for BuildError {
+    fn from(error: Error) -> Self {
+        BuildError { error, logs: vec![] }
+    }
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)?;
+        for log in self.logs.iter().filter(|log| log.status != BuildStatus::Success) {
+            write!(f, "\n\nDevice {:#x} ({}):\n{}", log.device, log.status, log.log.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Error returned when creating a program from binaries failed.
+///
+/// Besides the error code it carries the status of the binary of every device.
+#[derive(Clone, Debug)]
+pub struct BinaryError {
+    error: Error,
+    statuses: Vec<(DeviceId, Error)>,
+}
+
+impl BinaryError {
+    /// The error code returned by the OpenCL implementation.
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// The status of the binary of each device, `Error::Success` if it was loaded successfully.
+    pub fn statuses(&self) -> &[(DeviceId, Error)] {
+        &self.statuses
+    }
+}
+
+impl From<Error> for BinaryError {
+    fn from(error: Error) -> Self {
+        BinaryError { error, statuses: vec![] }
+    }
+}
+
+impl Display for BinaryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)?;
+        for (device, status) in self.statuses.iter().filter(|(_, status)| *status != Error::Success) {
+            write!(f, "\nDevice {:#x}: {}", device, status)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+/// An OpenCL program.
+///
+/// The program is retained when cloned and released when dropped.
+pub struct Program {
+    id: ProgramId,
+    context: Context,
+}
+
+impl Program {
+    /// Create a program from OpenCL C source code.
+    ///
+    /// The sources are concatenated to form the program.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - No sources were given or one of them is empty.
+    /// * `Error::InvalidContext` - An invalid context was passed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let program = Program::from_source(&context, &["__kernel void noop() {}"])?;
+    /// if let Err(error) = program.build(&[], "-cl-std=CL1.2") {
+    ///     eprintln!("{}", error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_source(context: &Context, sources: &[&str]) -> Result<Self> {
+        // OpenCL treats a length of zero as a nul-terminated string, which Rust strings are not
+        if sources.is_empty() || sources.iter().any(|source| source.is_empty()) {
+            return Err(Error::InvalidValue);
+        }
+
+        let strings: Vec<*const c_char> = sources.iter().map(|source| source.as_ptr() as *const c_char).collect();
+        let lengths: Vec<usize> = sources.iter().map(|source| source.len()).collect();
+        let mut result: i32 = 0;
+        let id = unsafe {
+            clCreateProgramWithSource(context.id(), sources.len() as u32, strings.as_ptr(), lengths.as_ptr(), &mut result)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone() })
+    }
+
+    /// Create a program from device specific binaries, e.g. previously obtained by [Program::binaries].
+    ///
+    /// The program still has to be built with [Program::build] before kernels can be created,
+    /// but this is much faster than compiling from source.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the program is created in.
+    /// * `devices` - The devices to load binaries for. Must be part of `context`.
+    /// * `binaries` - The binary for each device, in the same order as `devices`.
+    ///
+    /// # Errors
+    ///
+    /// On failure the returned [BinaryError] contains the binary status of each device.
+    /// The following error codes may be returned:
+    ///
+    /// * `Error::InvalidValue` - No devices were given, the number of binaries differs from the number of devices
+    ///   or one of the binaries is empty.
+    /// * `Error::InvalidContext` - An invalid context was passed.
+    /// * `Error::InvalidDevice` - One of the devices is not associated with the context.
+    /// * `Error::InvalidBinary` - One of the binaries is not valid for its device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let device = context.devices()[0];
+    /// let binary = std::fs::read("kernels.bin")?;
+    /// let program = Program::from_binaries(&context, &[device], &[&binary])?;
+    /// program.build(&[device], "")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_binaries(context: &Context, devices: &[DeviceId], binaries: &[&[u8]]) -> std::result::Result<Self, BinaryError> {
+        if devices.is_empty() || devices.len() != binaries.len() || binaries.iter().any(|binary| binary.is_empty()) {
+            return Err(Error::InvalidValue.into());
+        }
+
+        let pointers: Vec<*const u8> = binaries.iter().map(|binary| binary.as_ptr()).collect();
+        let lengths: Vec<usize> = binaries.iter().map(|binary| binary.len()).collect();
+        let mut statuses: Vec<i32> = vec![0; devices.len()];
+        let mut result: i32 = 0;
+        let id = unsafe {
+            clCreateProgramWithBinary(
+                context.id(), devices.len() as u32, devices.as_ptr(), lengths.as_ptr(), pointers.as_ptr(), statuses.as_mut_ptr(), &mut result,
+            )
+        };
+        if result != 0 {
+            let statuses = devices.iter().zip(statuses).map(|(&device, status)| (device, Error::from(status))).collect();
+            return Err(BinaryError { error: Error::from(result), statuses });
+        }
+
+        Ok(Self { id, context: context.clone() })
+    }
+
+    /// Create a program from built-in kernels provided by the devices.
+    ///
+    /// The program does not need to be built, its kernels can be created with [Kernel::new](crate::kernel::Kernel::new) right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the program is created in.
+    /// * `devices` - The devices to create the program for. If empty, all devices of the context are used.
+    /// * `names` - The names of the built-in kernels, as reported by [cl_get_device_built_in_kernels].
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - No kernel names were passed, or a kernel is not built into all devices.
+    /// * `Error::InvalidDevice` - A device is not part of the context.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::device::cl_get_device_built_in_kernels;
+    /// # use rusty_cl::kernel::Kernel;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::ACCELERATOR)?;
+    /// let device = context.devices()[0];
+    /// let names = cl_get_device_built_in_kernels(device)?;
+    /// let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    /// let program = Program::from_built_in_kernels(&context, &[device], &names)?;
+    /// let kernel = Kernel::new(&program, names[0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_built_in_kernels(context: &Context, devices: &[DeviceId], names: &[&str]) -> Result<Self> {
+        if names.is_empty() || names.iter().any(|name| name.is_empty() || name.contains(';')) {
+            return Err(Error::InvalidValue);
+        }
+        let devices = if devices.is_empty() { context.devices() } else { devices };
+        for &device in devices {
+            let available = cl_get_device_built_in_kernels(device)?;
+            if !names.iter().all(|name| available.iter().any(|kernel| kernel == name)) {
+                return Err(Error::InvalidValue);
+            }
+        }
+
+        let names = CString::new(names.join(";")).map_err(|_| Error::InvalidValue)?;
+        let mut result: i32 = 0;
+        let id = unsafe {
+            clCreateProgramWithBuiltInKernels(context.id(), devices.len() as u32, devices.as_ptr(), names.as_ptr(), &mut result)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone() })
+    }
+
+    /// Create a program from an intermediate language module, e.g. SPIR-V.
+    ///
+    /// On platforms supporting OpenCL 2.1 or newer `clCreateProgramWithIL` is used if the loaded library exports it,
+    /// otherwise `clCreateProgramWithILKHR` of the `cl_khr_il_program` extension.
+    /// The program still has to be built with [Program::build] before kernels can be created.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The module is empty or invalid.
+    /// * `Error::InvalidContext` - An invalid context was passed.
+    /// * `Error::InvalidOperation` - No device in the context supports an intermediate language,
+    ///   or the platform supports neither OpenCL 2.1 nor `cl_khr_il_program`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let module = std::fs::read("kernels.spv")?;
+    /// let program = Program::from_il(&context, &module)?;
+    /// program.build(&[], "")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_il(context: &Context, il: &[u8]) -> Result<Self> {
+        if il.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+
+        let mut supported = false;
+        for &device in context.devices() {
+            supported |= !cl_get_device_ils(device)?.is_empty();
+        }
+        if !supported {
+            return Err(Error::InvalidOperation);
+        }
+
+        let platform = Platform::get(context.platform())?;
+        let version = platform.version();
+        let create: CreateProgramWithILKHR = if let Some(create) = clCreateProgramWithIL().filter(|_| (version.major(), version.minor()) >= (2, 1)) {
+            create
+        } else if platform.extensions().iter().any(|extension| extension == "cl_khr_il_program") {
+            let name = CString::new("clCreateProgramWithILKHR").unwrap();
+            let address = unsafe { clGetExtensionFunctionAddressForPlatform(platform.id(), name.as_ptr()) };
+            if address.is_null() {
+                return Err(Error::InvalidOperation);
+            }
+            unsafe { std::mem::transmute::<*mut c_void, CreateProgramWithILKHR>(address) }
+        } else {
+            return Err(Error::InvalidOperation);
+        };
+
+        let mut result: i32 = 0;
+        let id = unsafe { create(context.id(), il.as_ptr() as *const c_void, il.len(), &mut result) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone() })
+    }
+
+    /// Build the program for the given devices using the given build options.
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - The devices to build the program for. If empty, the program is built for all devices of its context.
+    /// * `options` - The build options, e.g. `-cl-std=CL2.0 -D N=16`.
+    ///
+    /// # Errors
+    ///
+    /// On failure the returned [BuildError] contains the build status and build log of each device.
+    /// The following error codes may be returned:
+    ///
+    /// * `Error::InvalidDevice` - One of the devices is not associated with the program.
+    /// * `Error::InvalidBuildOptions` - The build options are invalid.
+    /// * `Error::CompilerNotAvailable` - No compiler is available for one of the devices.
+    /// * `Error::BuildProgramFailure` - Compiling or linking the program failed.
+    /// * `Error::InvalidOperation` - Kernel objects are attached to the program.
+    pub fn build(&self, devices: &[DeviceId], options: &str) -> std::result::Result<(), BuildError> {
+        let options = CString::new(options).map_err(|_| Error::InvalidBuildOptions)?;
+        let device_list = if devices.is_empty() { std::ptr::null() } else { devices.as_ptr() };
+        let result = unsafe {
+            clBuildProgram(self.id, devices.len() as u32, device_list, options.as_ptr(), None, std::ptr::null_mut())
+        };
+        if result != 0 {
+            return Err(self.build_error(Error::from(result), devices));
+        }
+        Ok(())
+    }
+
+    /// Compile the program for the given devices without linking it.
+    ///
+    /// The compiled program can be linked with other compiled programs and libraries using [Program::link].
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - The devices to compile the program for. If empty, the program is compiled for all devices of its context.
+    /// * `options` - The compiler options, e.g. `-cl-std=CL2.0 -D N=16`.
+    /// * `headers` - Programs created from source which are embedded as headers, together with the name
+    ///   under which they are included, e.g. `("common/math.h", &math)` for `#include "common/math.h"`.
+    ///
+    /// # Errors
+    ///
+    /// On failure the returned [BuildError] contains the build status and build log of each device.
+    /// The following error codes may be returned:
+    ///
+    /// * `Error::InvalidDevice` - One of the devices is not associated with the program.
+    /// * `Error::InvalidCompilerOptions` - The compiler options are invalid.
+    /// * `Error::InvalidValue` - One of the header names contains a nul byte.
+    /// * `Error::CompilerNotAvailable` - No compiler is available for one of the devices.
+    /// * `Error::CompileProgramFailure` - Compiling the program failed.
+    /// * `Error::InvalidOperation` - Kernel objects are attached to the program.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::types::DeviceType;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let header = Program::from_source(&context, &["float square(float x) { return x * x; }"])?;
+    /// let kernels = Program::from_source(&context, &[r#"
+    ///     #include "square.h"
+    ///     __kernel void squares(__global float *a) { a[get_global_id(0)] = square(a[get_global_id(0)]); }
+    /// "#])?;
+    /// kernels.compile(&[], "", &[("square.h", &header)])?;
+    /// let program = Program::link(&context, &[], "", &[&kernels])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compile(&self, devices: &[DeviceId], options: &str, headers: &[(&str, &Program)]) -> std::result::Result<(), BuildError> {
+        let options = CString::new(options).map_err(|_| Error::InvalidCompilerOptions)?;
+        let names = headers.iter().map(|(name, _)| CString::new(*name)).collect::<std::result::Result<Vec<CString>, _>>()
+            .map_err(|_| Error::InvalidValue)?;
+        let name_pointers: Vec<*const c_char> = names.iter().map(|name| name.as_ptr()).collect();
+        let header_ids: Vec<ProgramId> = headers.iter().map(|(_, header)| header.id).collect();
+        require(if devices.is_empty() { self.context.devices() } else { devices }, DeviceInfo::CompilerAvailable, Error::CompilerNotAvailable)?;
+
+        let device_list = if devices.is_empty() { std::ptr::null() } else { devices.as_ptr() };
+        let (header_list, name_list) = if headers.is_empty() {
+            (std::ptr::null(), std::ptr::null())
+        } else {
+            (header_ids.as_ptr(), name_pointers.as_ptr())
+        };
+        let result = unsafe {
+            clCompileProgram(
+                self.id, devices.len() as u32, device_list, options.as_ptr(), headers.len() as u32, header_list, name_list, None, std::ptr::null_mut(),
+            )
+        };
+        if result != 0 {
+            return Err(self.build_error(Error::from(result), devices));
+        }
+        Ok(())
+    }
+
+    /// Link compiled programs and libraries into a new executable program or library.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the program is created in.
+    /// * `devices` - The devices to link the program for. If empty, the program is linked for all devices of `context`.
+    /// * `options` - The linker options, e.g. `-create-library`.
+    /// * `programs` - The compiled programs and libraries to link.
+    ///
+    /// # Errors
+    ///
+    /// On failure the returned [BuildError] contains the build status and build log of each device,
+    /// if the OpenCL implementation created a program object.
+    /// The following error codes may be returned:
+    ///
+    /// * `Error::InvalidValue` - No programs were given.
+    /// * `Error::InvalidContext` - An invalid context was passed.
+    /// * `Error::InvalidDevice` - One of the devices is not associated with the context.
+    /// * `Error::InvalidLinkerOptions` - The linker options are invalid.
+    /// * `Error::InvalidOperation` - One of the programs was not compiled for all devices.
+    /// * `Error::LinkerNotAvailable` - No linker is available for one of the devices.
+    /// * `Error::LinkProgramFailure` - Linking the programs failed.
+    pub fn link(context: &Context, devices: &[DeviceId], options: &str, programs: &[&Program]) -> std::result::Result<Self, BuildError> {
+        if programs.is_empty() {
+            return Err(Error::InvalidValue.into());
+        }
+        let options = CString::new(options).map_err(|_| Error::InvalidLinkerOptions)?;
+        require(if devices.is_empty() { context.devices() } else { devices }, DeviceInfo::LinkerAvailable, Error::LinkerNotAvailable)?;
+
+        let program_ids: Vec<ProgramId> = programs.iter().map(|program| program.id).collect();
+        let device_list = if devices.is_empty() { std::ptr::null() } else { devices.as_ptr() };
+        let mut result: i32 = 0;
+        let id = unsafe {
+            clLinkProgram(
+                context.id(), devices.len() as u32, device_list, options.as_ptr(), programs.len() as u32, program_ids.as_ptr(), None, std::ptr::null_mut(), &mut result,
+            )
+        };
+        if result != 0 {
+            if id == 0 {
+                return Err(Error::from(result).into());
+            }
+            let program = Self { id, context: context.clone() };
+            return Err(program.build_error(Error::from(result), devices));
+        }
+
+        Ok(Self { id, context: context.clone() })
+    }
+
+    pub(crate) fn build_error(&self, error: Error, devices: &[DeviceId]) -> BuildError {
+        let devices = if devices.is_empty() { self.context.devices() } else { devices };
+        let logs = devices.iter().filter_map(|&device| {
+            Some(BuildLog {
+                device,
+                status: self.build_status(device).ok()?,
+                log: self.build_log(device).ok()?,
+            })
+        }).collect();
+        BuildError { error, logs }
+    }
+
+    /// The native handle of the program.
+    pub fn id(&self) -> ProgramId {
+        self.id
+    }
+
+    /// The context the program was created in.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// The devices associated with the program.
+    pub fn devices(&self) -> Result<Vec<DeviceId>> {
+        unsafe { info::get_vec(|size, value, size_ret| clGetProgramInfo(self.id, ProgramInfo::Devices, size, value, size_ret)) }
+    }
+
+    /// The binary of the program for each of its devices, in the same order as [Program::devices].
+    ///
+    /// The binary of a device the program was not built for is empty.
+    pub fn binaries(&self) -> Result<Vec<(DeviceId, Vec<u8>)>> {
+        let devices = self.devices()?;
+        let sizes: Vec<usize> = unsafe {
+            info::get_vec(|size, value, size_ret| clGetProgramInfo(self.id, ProgramInfo::BinarySizes, size, value, size_ret))
+        }?;
+        if sizes.len() != devices.len() {
+            return Err(Error::InvalidValue);
+        }
+
+        let mut binaries: Vec<Vec<u8>> = sizes.iter().map(|&size| vec![0; size]).collect();
+        let mut pointers: Vec<*mut u8> = binaries
+            .iter_mut()
+            .map(|binary| if binary.is_empty() { std::ptr::null_mut() } else { binary.as_mut_ptr() })
+            .collect();
+        let size = pointers.len() * std::mem::size_of::<*mut u8>();
+        let result = unsafe {
+            clGetProgramInfo(self.id, ProgramInfo::Binaries, size, pointers.as_mut_ptr() as *mut c_void, std::ptr::null_mut())
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(devices.into_iter().zip(binaries).collect())
+    }
+
+    /// The source code the program was created from.
+    pub fn source(&self) -> Result<String> {
+        unsafe { info::get_string(|size, value, size_ret| clGetProgramInfo(self.id, ProgramInfo::Source, size, value, size_ret)) }
+    }
+
+    /// The build status of the program for the given device.
+    pub fn build_status(&self, device: DeviceId) -> Result<BuildStatus> {
+        let status: i32 = unsafe {
+            info::get_value(|size, value: *mut c_void, size_ret| clGetProgramBuildInfo(self.id, device, ProgramBuildInfo::Status, size, value, size_ret))
+        }?;
+        Ok(BuildStatus::from(status))
+    }
+
+    /// The build options of the last build of the program for the given device.
+    pub fn build_options(&self, device: DeviceId) -> Result<String> {
+        unsafe { info::get_string(|size, value, size_ret| clGetProgramBuildInfo(self.id, device, ProgramBuildInfo::Options, size, value, size_ret)) }
+    }
+
+    /// The build log of the last build of the program for the given device.
+    pub fn build_log(&self, device: DeviceId) -> Result<String> {
+        unsafe { info::get_string(|size, value, size_ret| clGetProgramBuildInfo(self.id, device, ProgramBuildInfo::Log, size, value, size_ret)) }
+    }
+}
+
+/// Make sure the given boolean device info is set for all devices, otherwise return the given error.
+fn require(devices: &[DeviceId], name: DeviceInfo, error: Error) -> Result<()> {
+    for &device in devices {
+        if unsafe { cl_get_device_info::<u32>(device, name) }? == 0 {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+impl Clone for Program {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainProgram(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id: self.id, context: self.context.clone() }
+    }
+}
+
+impl Drop for Program {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseProgram(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::Kernel;
+    use crate::platform::cl_get_platform_ids;
+    use crate::types::DeviceType;
+
+    #[test]
+    fn build_error_display() {
+        let error = BuildError {
+            error: Error::BuildProgramFailure,
+            logs: vec![
+                BuildLog { device: 0x10, status: BuildStatus::Success, log: String::new() },
+                BuildLog { device: 0x20, status: BuildStatus::Error, log: "error: unknown type name 'flaot'\n".to_string() },
+            ],
+        };
+        assert_eq!(
+            error.to_string(),
+            "There was a failure to build the program executable.\n\nDevice 0x20 (CL_BUILD_ERROR):\nerror: unknown type name 'flaot'",
+        );
+    }
+
+    #[test]
+    fn binary_error_display() {
+        let error = BinaryError {
+            error: Error::InvalidBinary,
+            statuses: vec![(0x10, Error::Success), (0x20, Error::InvalidBinary)],
+        };
+        assert_eq!(
+            error.to_string(),
+            "An invalid program binary was passed for a device.\nDevice 0x20: An invalid program binary was passed for a device.",
+        );
+    }
+
+    #[test]
+    fn build_programs() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let source = "__kernel void add(__global float *a, float b) { a[get_global_id(0)] += b; }";
+            let program = Program::from_source(&context, &[source]).unwrap();
+            assert_eq!(program.source().unwrap(), source);
+            program.build(&[], "-D UNUSED=1").unwrap();
+            for &device in context.devices() {
+                assert_eq!(program.build_status(device).unwrap(), BuildStatus::Success);
+                assert_eq!(program.build_options(device).unwrap(), "-D UNUSED=1");
+            }
+
+            let program = Program::from_source(&context, &["__kernel void broken(flaot x) {}"]).unwrap();
+            let error = program.build(&[], "").unwrap_err();
+            assert_eq!(error.error(), Error::BuildProgramFailure);
+            assert_eq!(error.logs().len(), context.devices().len());
+            assert!(error.logs().iter().all(|log| log.status() == BuildStatus::Error));
+
+            assert_eq!(Program::from_source(&context, &[]).err(), Some(Error::InvalidValue));
+            assert_eq!(Program::from_source(&context, &[""]).err(), Some(Error::InvalidValue));
+            assert_eq!(Program::from_source(&context, &[source, ""]).err(), Some(Error::InvalidValue));
+        }
+    }
+
+    #[test]
+    fn compile_and_link() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let header = Program::from_source(&context, &["float square(float x) { return x * x; }"]).unwrap();
+            let kernels = Program::from_source(&context, &[
+                "#include \"math/square.h\"\n__kernel void squares(__global float *a) { a[get_global_id(0)] = square(a[get_global_id(0)]); }",
+            ]).unwrap();
+            match kernels.compile(&[], "", &[("math/square.h", &header)]) {
+                Err(error) if error.error() == Error::CompilerNotAvailable => continue,
+                result => result.unwrap(),
+            }
+            for &device in context.devices() {
+                assert_eq!(kernels.build_status(device).unwrap(), BuildStatus::Success);
+            }
+
+            let program = match Program::link(&context, &[], "", &[&kernels]) {
+                Err(error) if error.error() == Error::LinkerNotAvailable => continue,
+                result => result.unwrap(),
+            };
+            for &device in context.devices() {
+                assert_eq!(program.build_status(device).unwrap(), BuildStatus::Success);
+            }
+
+            let error = kernels.compile(&[], "", &[]).unwrap_err();
+            assert_eq!(error.error(), Error::CompileProgramFailure);
+            assert_eq!(Program::link(&context, &[], "", &[]).err().unwrap().error(), Error::InvalidValue);
+        }
+    }
+
+    #[test]
+    fn reload_binaries() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let program = Program::from_source(&context, &["__kernel void noop() {}"]).unwrap();
+            program.build(&[], "").unwrap();
+            let binaries = program.binaries().unwrap();
+            assert_eq!(binaries.len(), context.devices().len());
+
+            let devices: Vec<DeviceId> = binaries.iter().map(|(device, _)| *device).collect();
+            let binaries: Vec<&[u8]> = binaries.iter().map(|(_, binary)| binary.as_slice()).collect();
+            let program = Program::from_binaries(&context, &devices, &binaries).unwrap();
+            program.build(&[], "").unwrap();
+
+            let error = Program::from_binaries(&context, &devices[..1], &[b"garbage"]).err().unwrap();
+            assert_eq!(error.error(), Error::InvalidBinary);
+            assert_eq!(error.statuses(), &[(devices[0], Error::InvalidBinary)]);
+
+            assert_eq!(Program::from_binaries(&context, &devices, &[]).err().unwrap().error(), Error::InvalidValue);
+        }
+    }
+
+    #[test]
+    fn built_in_kernels() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            assert_eq!(Program::from_built_in_kernels(&context, &[], &[]).err(), Some(Error::InvalidValue));
+            assert_eq!(Program::from_built_in_kernels(&context, &[], &["no_such_kernel"]).err(), Some(Error::InvalidValue));
+
+            for &device in context.devices() {
+                let names = cl_get_device_built_in_kernels(device).unwrap();
+                if names.is_empty() {
+                    continue;
+                }
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                let program = Program::from_built_in_kernels(&context, &[device], &names).unwrap();
+                for name in names {
+                    assert_eq!(Kernel::new(&program, name).unwrap().name(), name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,716 @@
+use std::ffi::c_void;
+use std::fmt::{Display, Formatter};
+use crate::context::Context;
+use crate::device::{cl_get_device_info, cl_get_device_info_vec};
+use crate::event::{self, Event};
+use crate::info;
+use crate::kernel::Kernel;
+use crate::memory::{Location, MemObject};
+use crate::native::{clCreateCommandQueue, clCreateCommandQueueWithProperties, clEnqueueBarrierWithWaitList, clEnqueueMarkerWithWaitList, clEnqueueMigrateMemObjects, clEnqueueNDRangeKernel, clFinish, clFlush, clGetCommandQueueInfo, clReleaseCommandQueue, clRetainCommandQueue};
+use crate::platform::Platform;
+use crate::result::{Error, Result};
+use crate::types::{CommandQueueId, CommandQueueInfo, CommandQueueProperties, DeviceId, DeviceInfo, EventId, MemId, MigrationFlags, QueueProperty, QUEUE_PROPERTIES};
+
+/// Get a command queue info for the given command queue.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the type of the command queue info.
+pub unsafe fn cl_get_command_queue_info<T: Sized>(queue: CommandQueueId, name: CommandQueueInfo) -> Result<T> {
+    info::get_value(|size, value: *mut c_void, size_ret| clGetCommandQueueInfo(queue, name, size, value, size_ret))
+}
+
+/// The index space of a kernel launch with 1, 2 or 3 dimensions.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct NdRange {
+    global: Vec<usize>,
+    local: Option<Vec<usize>>,
+    offset: Option<Vec<usize>>,
+    non_uniform: bool,
+}
+
+impl NdRange {
+    /// An index space with the given global work size.
+    ///
+    /// The local work size is chosen by the OpenCL implementation.
+    pub fn new(global: &[usize]) -> Self {
+        Self { global: global.to_vec(), local: None, offset: None, non_uniform: false }
+    }
+
+    /// Use the given local work size, i.e. the size of a work-group.
+    pub fn with_local(mut self, local: &[usize]) -> Self {
+        self.local = Some(local.to_vec());
+        self
+    }
+
+    /// Use the given global work offset.
+    pub fn with_offset(mut self, offset: &[usize]) -> Self {
+        self.offset = Some(offset.to_vec());
+        self
+    }
+
+    /// Allow a global work size which is not a multiple of the local work size.
+    ///
+    /// Non-uniform work-groups require a device supporting them, i.e. an OpenCL 2.x device or an OpenCL 3.0 device
+    /// reporting `DeviceInfo::NonUniformWorkGroupSupport`, and a program built with `-cl-std=CL2.0` or newer.
+    /// The latter cannot be checked, so only opt in for programs built accordingly.
+    pub fn with_non_uniform_work_groups(mut self) -> Self {
+        self.non_uniform = true;
+        self
+    }
+
+    /// The number of dimensions.
+    pub fn dimensions(&self) -> usize {
+        self.global.len()
+    }
+
+    /// The global work size.
+    pub fn global(&self) -> &[usize] {
+        &self.global
+    }
+
+    /// The local work size, if specified.
+    pub fn local(&self) -> Option<&[usize]> {
+        self.local.as_deref()
+    }
+
+    /// The global work offset, if specified.
+    pub fn offset(&self) -> Option<&[usize]> {
+        self.offset.as_deref()
+    }
+
+    /// Check the index space against the work-group limits of a device.
+    fn validate(&self, limits: &WorkLimits) -> std::result::Result<(), NdRangeError> {
+        let dimensions = self.dimensions();
+        if !(1..=3).contains(&dimensions) || dimensions > limits.max_dimensions as usize {
+            return Err(NdRangeError::InvalidDimensions { dimensions, max: limits.max_dimensions.min(3) });
+        }
+        if let Some(dimension) = self.global.iter().position(|&size| size == 0) {
+            return Err(NdRangeError::EmptyGlobalSize { dimension });
+        }
+        if let Some(offset) = &self.offset {
+            if offset.len() != dimensions {
+                return Err(NdRangeError::MismatchedDimensions { name: "global work offset", dimensions: offset.len(), expected: dimensions });
+            }
+        }
+
+        let local = match &self.local {
+            Some(local) => local,
+            None => return Ok(()),
+        };
+        if local.len() != dimensions {
+            return Err(NdRangeError::MismatchedDimensions { name: "local work size", dimensions: local.len(), expected: dimensions });
+        }
+        for (dimension, (&size, &global)) in local.iter().zip(&self.global).enumerate() {
+            let max = limits.max_item_sizes.get(dimension).copied().unwrap_or(0);
+            if size == 0 || size > max {
+                return Err(NdRangeError::WorkItemSizeExceeded { dimension, size, max });
+            }
+            if !(self.non_uniform && limits.non_uniform_work_groups) && global % size != 0 {
+                return Err(NdRangeError::NonUniformWorkGroup { dimension, global, local: size });
+            }
+        }
+        let size: usize = local.iter().product();
+        if size > limits.max_group_size {
+            return Err(NdRangeError::WorkGroupSizeExceeded { size, max: limits.max_group_size });
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when launching a kernel failed.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum NdRangeError {
+    /// The OpenCL implementation rejected the launch.
+    Error(Error),
+
+    /// The index space has an unsupported number of dimensions.
+    InvalidDimensions {
+        /// The number of dimensions of the index space.
+        dimensions: usize,
+        /// The maximum number of dimensions supported by the device.
+        max: u32,
+    },
+
+    /// The global work size is zero in one dimension.
+    EmptyGlobalSize {
+        /// The dimension with the empty size.
+        dimension: usize,
+    },
+
+    /// The local work size or global offset has a different number of dimensions than the global work size.
+    MismatchedDimensions {
+        /// What has the wrong number of dimensions.
+        name: &'static str,
+        /// The number of dimensions given.
+        dimensions: usize,
+        /// The number of dimensions of the global work size.
+        expected: usize,
+    },
+
+    /// The local work size exceeds `DeviceInfo::MaxWorkItemSizes` in one dimension.
+    WorkItemSizeExceeded {
+        /// The offending dimension.
+        dimension: usize,
+        /// The local work size in that dimension.
+        size: usize,
+        /// The maximum supported by the device in that dimension.
+        max: usize,
+    },
+
+    /// The total number of work-items in a work-group exceeds `DeviceInfo::MaxWorkGroupSize`.
+    WorkGroupSizeExceeded {
+        /// The number of work-items in a work-group.
+        size: usize,
+        /// The maximum supported by the device.
+        max: usize,
+    },
+
+    /// The global work size is not a multiple of the local work size, but non-uniform work-groups
+    /// were not enabled with [NdRange::with_non_uniform_work_groups] or are not supported by the device.
+    NonUniformWorkGroup {
+        /// The offending dimension.
+        dimension: usize,
+        /// The global work size in that dimension.
+        global: usize,
+        /// The local work size in that dimension.
+        local: usize,
+    },
+}
+
+impl From<Error> for NdRangeError {
+    fn from(error: Error) -> Self {
+        NdRangeError::Error(error)
+    }
+}
+
+impl Display for NdRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            NdRangeError::Error(error) => write!(f, "{}", error),
+            NdRangeError::InvalidDimensions { dimensions, max } =>
+                write!(f, "The index space has {} dimensions, but the device supports 1 to {}.", dimensions, max),
+            NdRangeError::EmptyGlobalSize { dimension } =>
+                write!(f, "The global work size is zero in dimension {}.", dimension),
+            NdRangeError::MismatchedDimensions { name, dimensions, expected } =>
+                write!(f, "The {} has {} dimensions, but the global work size has {}.", name, dimensions, expected),
+            NdRangeError::WorkItemSizeExceeded { dimension, size, max } =>
+                write!(f, "The local work size {} in dimension {} is not within 1 to {} (MaxWorkItemSizes).", size, dimension, max),
+            NdRangeError::WorkGroupSizeExceeded { size, max } =>
+                write!(f, "The work-group has {} work-items, but the device supports at most {} (MaxWorkGroupSize).", size, max),
+            NdRangeError::NonUniformWorkGroup { dimension, global, local } =>
+                write!(f, "The global work size {} in dimension {} is not a multiple of the local work size {}.", global, dimension, local),
+        }
+    }
+}
+
+impl std::error::Error for NdRangeError {}
+
+/// The work-group limits of a device.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+struct WorkLimits {
+    max_dimensions: u32,
+    max_item_sizes: Vec<usize>,
+    max_group_size: usize,
+    non_uniform_work_groups: bool,
+}
+
+impl WorkLimits {
+    fn query(device: DeviceId, version: u8) -> Result<Self> {
+        unsafe {
+            Ok(Self {
+                max_dimensions: cl_get_device_info(device, DeviceInfo::MaxWorkItemDimensions)?,
+                max_item_sizes: cl_get_device_info_vec(device, DeviceInfo::MaxWorkItemSizes)?,
+                max_group_size: cl_get_device_info(device, DeviceInfo::MaxWorkGroupSize)?,
+                non_uniform_work_groups: match version {
+                    0..=1 => false,
+                    // mandatory on OpenCL 2.x devices, assuming the program was built with -cl-std=CL2.0 or newer
+                    2 => true,
+                    _ => cl_get_device_info::<u32>(device, DeviceInfo::NonUniformWorkGroupSupport).is_ok_and(|supported| supported != 0),
+                },
+            })
+        }
+    }
+}
+
+/// An OpenCL command queue.
+///
+/// The command queue is retained when cloned and released when dropped.
+pub struct CommandQueue {
+    id: CommandQueueId,
+    context: Context,
+    device: DeviceId,
+    properties: CommandQueueProperties,
+    limits: WorkLimits,
+}
+
+impl CommandQueue {
+    /// Create a command queue for the given device in the given context.
+    ///
+    /// On platforms supporting OpenCL 2.0 or newer `clCreateCommandQueueWithProperties` is used,
+    /// otherwise the queue is created with the deprecated `clCreateCommandQueue`.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the queue is created in.
+    /// * `device` - The device the queue submits work to. Must be part of `context`.
+    /// * `properties` - Whether commands may be executed out of order and whether they are profiled.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidContext` - An invalid context was passed.
+    /// * `Error::InvalidDevice` - The device is not associated with the context.
+    /// * `Error::InvalidQueueProperties` - The properties are not supported by the device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::PROFILING_ENABLE)?;
+    /// assert!(queue.profiling_enabled());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(context: &Context, device: DeviceId, properties: CommandQueueProperties) -> Result<Self> {
+        let version = Platform::get(context.platform())?.version().major();
+        let limits = WorkLimits::query(device, version)?;
+
+        let mut result: i32 = 0;
+        let id = match clCreateCommandQueueWithProperties().filter(|_| version >= 2) {
+            Some(create) => {
+                let queue_properties: [QueueProperty; 3] = [QUEUE_PROPERTIES, properties.raw(), 0];
+                unsafe { create(context.id(), device, queue_properties.as_ptr(), &mut result) }
+            }
+            None => unsafe { clCreateCommandQueue(context.id(), device, properties.raw(), &mut result) },
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone(), device, properties, limits })
+    }
+
+    /// The native handle of the command queue.
+    pub fn id(&self) -> CommandQueueId {
+        self.id
+    }
+
+    /// The context the command queue was created in.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// The device the command queue submits work to.
+    pub fn device(&self) -> DeviceId {
+        self.device
+    }
+
+    /// The properties the command queue was created with.
+    pub fn properties(&self) -> CommandQueueProperties {
+        self.properties
+    }
+
+    /// Whether commands may be executed out of order.
+    pub fn out_of_order(&self) -> bool {
+        self.properties.contains(CommandQueueProperties::OUT_OF_ORDER_EXEC_MODE_ENABLE)
+    }
+
+    /// Whether profiling of commands is enabled.
+    pub fn profiling_enabled(&self) -> bool {
+        self.properties.contains(CommandQueueProperties::PROFILING_ENABLE)
+    }
+
+    /// The current reference count of the command queue.
+    ///
+    /// # Remarks
+    ///
+    /// The reference count should be considered immediately stale.
+    /// It is only useful for identifying memory leaks.
+    pub fn reference_count(&self) -> Result<u32> {
+        unsafe { cl_get_command_queue_info(self.id, CommandQueueInfo::ReferenceCount) }
+    }
+
+    /// Issue all previously queued commands to the device.
+    ///
+    /// There is no guarantee that the commands have completed once this function returns.
+    pub fn flush(&self) -> Result<()> {
+        let result = unsafe { clFlush(self.id) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+
+    /// Block until all previously queued commands have been issued and have completed.
+    pub fn finish(&self) -> Result<()> {
+        let result = unsafe { clFinish(self.id) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(())
+    }
+
+    /// Enqueue a marker which completes once all events in the wait list have completed.
+    ///
+    /// If the wait list is empty, the marker completes once all previously enqueued commands have completed.
+    /// Unlike a barrier, a marker does not block the execution of subsequently enqueued commands.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidContext` - The events in the wait list belong to a different context.
+    /// * `Error::InvalidEventWaitList` - An event in the wait list is invalid.
+    pub fn enqueue_marker(&self, wait_list: &[Event]) -> Result<Event> {
+        self.enqueue_sync(clEnqueueMarkerWithWaitList, wait_list)
+    }
+
+    /// Enqueue a barrier which blocks all subsequently enqueued commands
+    /// until all events in the wait list have completed.
+    ///
+    /// If the wait list is empty, the barrier waits for all previously enqueued commands.
+    /// This is mostly useful to order commands on out-of-order queues.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidContext` - The events in the wait list belong to a different context.
+    /// * `Error::InvalidEventWaitList` - An event in the wait list is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::event::UserEvent;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::OUT_OF_ORDER_EXEC_MODE_ENABLE)?;
+    ///
+    /// // Hold back all further commands until the input has arrived on the host.
+    /// let input_ready = UserEvent::new(&context)?;
+    /// queue.enqueue_barrier(&[input_ready.event().clone()])?;
+    /// // ... enqueue commands, receive the input ...
+    /// input_ready.complete()?;
+    /// queue.finish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enqueue_barrier(&self, wait_list: &[Event]) -> Result<Event> {
+        self.enqueue_sync(clEnqueueBarrierWithWaitList, wait_list)
+    }
+
+    /// Enqueue migrating memory objects to the device of the queue, or to the host with `MigrationFlags::HOST`.
+    ///
+    /// Migrating memory objects ahead of the commands using them allows overlapping the transfer with other work
+    /// instead of an implicit migration when the commands start.
+    /// The [Residency](crate::memory::Residency) of tracked memory objects is updated accordingly.
+    ///
+    /// # Arguments
+    ///
+    /// * `objects` - The memory objects to migrate.
+    /// * `flags` - Where to migrate to and whether the contents are needed afterwards.
+    /// * `wait_list` - Events which must complete before the migration starts.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - No memory objects or unknown flags were passed.
+    /// * `Error::InvalidContext` - A memory object belongs to a different context than the queue.
+    /// * `Error::MemObjectAllocationFailure` - The memory for the migrated objects could not be allocated.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::buffer::Buffer;
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::memory::{Location, MemObject};
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, MemFlags, MigrationFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queues = context.devices().iter()
+    ///     .map(|&device| CommandQueue::new(&context, device, CommandQueueProperties::NONE))
+    ///     .collect::<rusty_cl::result::Result<Vec<_>>>()?;
+    /// let input = Buffer::<f32>::new(&context, MemFlags::READ_ONLY, 1 << 20)?;
+    /// let output = Buffer::<f32>::new(&context, MemFlags::WRITE_ONLY, 1 << 20)?;
+    ///
+    /// // Prefetch the input to the second device, the output is overwritten anyway.
+    /// queues[1].enqueue_migrate(&[&input], MigrationFlags::NONE, &[])?;
+    /// queues[1].enqueue_migrate(&[&output], MigrationFlags::CONTENT_UNDEFINED, &[])?;
+    /// assert_eq!(input.residency().unwrap().location(), Location::Device(queues[1].device()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enqueue_migrate(&self, objects: &[&dyn MemObject], flags: MigrationFlags, wait_list: &[Event]) -> Result<Event> {
+        if objects.is_empty() || flags.raw() & !(MigrationFlags::HOST | MigrationFlags::CONTENT_UNDEFINED).raw() != 0 {
+            return Err(Error::InvalidValue);
+        }
+
+        let ids: Vec<MemId> = objects.iter().map(|object| object.mem_id()).collect();
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueMigrateMemObjects(self.id, ids.len() as u32, ids.as_ptr(), flags.raw(), num_events, events, &mut event)
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        let location = if flags.contains(MigrationFlags::HOST) { Location::Host } else { Location::Device(self.device) };
+        for residency in objects.iter().filter_map(|object| object.residency()) {
+            residency.set(location);
+        }
+        Ok(Event::from_id(event))
+    }
+
+    fn enqueue_sync(&self, enqueue: unsafe extern "C" fn(CommandQueueId, u32, *const EventId, *mut EventId) -> i32, wait_list: &[Event]) -> Result<Event> {
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe { enqueue(self.id, num_events, events, &mut event) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(Event::from_id(event))
+    }
+
+    /// Enqueue the execution of a kernel over the given index space.
+    ///
+    /// Before calling into OpenCL the index space is checked against the limits of the device,
+    /// i.e. `DeviceInfo::MaxWorkItemDimensions`, `DeviceInfo::MaxWorkItemSizes` and `DeviceInfo::MaxWorkGroupSize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel` - The kernel to execute. All of its arguments must have been set.
+    /// * `range` - The index space to execute the kernel over.
+    /// * `wait_list` - Events which must complete before the kernel starts.
+    ///
+    /// # Errors
+    ///
+    /// Besides the descriptive validation errors of [NdRangeError], the following errors may be returned:
+    ///
+    /// * `Error::InvalidProgramExecutable` - The program of the kernel is not built for the device of the queue.
+    /// * `Error::InvalidKernelArgs` - Not all kernel arguments have been set.
+    /// * `Error::InvalidWorkGroupSize` - The local work size does not match the one required by the kernel.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::kernel::Kernel;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::program::Program;
+    /// # use rusty_cl::queue::{CommandQueue, NdRange};
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    /// let program = Program::from_source(&context, &["__kernel void noop() {}"])?;
+    /// program.build(&[], "")?;
+    /// let kernel = Kernel::new(&program, "noop")?;
+    ///
+    /// let event = queue.enqueue_nd_range_kernel(&kernel, &NdRange::new(&[1920, 1088]).with_local(&[16, 16]), &[])?;
+    /// event.wait()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enqueue_nd_range_kernel(&self, kernel: &Kernel, range: &NdRange, wait_list: &[Event]) -> std::result::Result<Event, NdRangeError> {
+        range.validate(&self.limits)?;
+
+        let local = range.local().map_or(std::ptr::null(), |local| local.as_ptr());
+        let offset = range.offset().map_or(std::ptr::null(), |offset| offset.as_ptr());
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            clEnqueueNDRangeKernel(
+                self.id, kernel.id(), range.dimensions() as u32, offset, range.global().as_ptr(), local,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(NdRangeError::Error(Error::from(result)));
+        }
+        kernel.used_by(self);
+        Ok(Event::from_id(event))
+    }
+}
+
+impl Clone for CommandQueue {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainCommandQueue(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id: self.id, context: self.context.clone(), device: self.device, properties: self.properties, limits: self.limits.clone() }
+    }
+}
+
+impl Drop for CommandQueue {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseCommandQueue(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::Buffer;
+    use crate::event::UserEvent;
+    use crate::platform::cl_get_platform_ids;
+    use crate::program::Program;
+    use crate::types::{DeviceType, ExecutionStatus, MemFlags};
+
+    fn limits(non_uniform_work_groups: bool) -> WorkLimits {
+        WorkLimits { max_dimensions: 3, max_item_sizes: vec![256, 256, 64], max_group_size: 256, non_uniform_work_groups }
+    }
+
+    #[test]
+    fn validate_nd_ranges() {
+        assert_eq!(NdRange::new(&[1024]).validate(&limits(false)), Ok(()));
+        assert_eq!(NdRange::new(&[1024, 768]).with_local(&[16, 16]).with_offset(&[0, 8]).validate(&limits(false)), Ok(()));
+        assert_eq!(NdRange::new(&[1000]).with_local(&[64]).with_non_uniform_work_groups().validate(&limits(true)), Ok(()));
+
+        assert_eq!(
+            NdRange::new(&[]).validate(&limits(false)),
+            Err(NdRangeError::InvalidDimensions { dimensions: 0, max: 3 }),
+        );
+        assert_eq!(
+            NdRange::new(&[1, 1, 1, 1]).validate(&limits(false)),
+            Err(NdRangeError::InvalidDimensions { dimensions: 4, max: 3 }),
+        );
+        assert_eq!(
+            NdRange::new(&[16, 0]).validate(&limits(false)),
+            Err(NdRangeError::EmptyGlobalSize { dimension: 1 }),
+        );
+        assert_eq!(
+            NdRange::new(&[16, 16]).with_local(&[16]).validate(&limits(false)),
+            Err(NdRangeError::MismatchedDimensions { name: "local work size", dimensions: 1, expected: 2 }),
+        );
+        assert_eq!(
+            NdRange::new(&[256, 256, 256]).with_local(&[1, 1, 128]).validate(&limits(false)),
+            Err(NdRangeError::WorkItemSizeExceeded { dimension: 2, size: 128, max: 64 }),
+        );
+        assert_eq!(
+            NdRange::new(&[1024, 1024]).with_local(&[32, 32]).validate(&limits(false)),
+            Err(NdRangeError::WorkGroupSizeExceeded { size: 1024, max: 256 }),
+        );
+        assert_eq!(
+            NdRange::new(&[1000]).with_local(&[64]).validate(&limits(false)),
+            Err(NdRangeError::NonUniformWorkGroup { dimension: 0, global: 1000, local: 64 }),
+        );
+        assert_eq!(
+            NdRange::new(&[1000]).with_local(&[64]).validate(&limits(true)),
+            Err(NdRangeError::NonUniformWorkGroup { dimension: 0, global: 1000, local: 64 }),
+        );
+        assert_eq!(
+            NdRange::new(&[1000]).with_local(&[64]).with_non_uniform_work_groups().validate(&limits(false)),
+            Err(NdRangeError::NonUniformWorkGroup { dimension: 0, global: 1000, local: 64 }),
+        );
+    }
+
+    #[test]
+    fn create_queues() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            for &device in context.devices() {
+                let queue = CommandQueue::new(&context, device, CommandQueueProperties::NONE).unwrap();
+                assert!(!queue.out_of_order());
+                assert!(!queue.profiling_enabled());
+                assert_eq!(unsafe { cl_get_command_queue_info::<DeviceId>(queue.id(), CommandQueueInfo::Device) }.unwrap(), device);
+                queue.flush().unwrap();
+                queue.finish().unwrap();
+
+                let queue = CommandQueue::new(&context, device, CommandQueueProperties::PROFILING_ENABLE).unwrap();
+                assert!(queue.profiling_enabled());
+                let properties: u64 = unsafe { cl_get_command_queue_info(queue.id(), CommandQueueInfo::Properties) }.unwrap();
+                assert_eq!(properties, queue.properties().raw());
+            }
+        }
+    }
+
+    #[test]
+    fn synchronize_queues() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE).unwrap();
+            let gate = UserEvent::new(&context).unwrap();
+            let barrier = queue.enqueue_barrier(&[gate.event().clone()]).unwrap();
+            let marker = queue.enqueue_marker(&[]).unwrap();
+            queue.flush().unwrap();
+            assert_ne!(marker.status(), Ok(ExecutionStatus::Complete));
+
+            gate.complete().unwrap();
+            marker.wait().unwrap();
+            assert_eq!(barrier.status(), Ok(ExecutionStatus::Complete));
+            assert_eq!(gate.complete(), Err(Error::InvalidOperation));
+
+            let gate = UserEvent::new(&context).unwrap();
+            let marker = queue.enqueue_marker(&[gate.event().clone()]).unwrap();
+            gate.fail(Error::OutOfResources).unwrap();
+            assert_eq!(marker.wait(), Err(Error::ExecStatusErrorForEventsInWaitList));
+            assert_eq!(gate.status(), Err(Error::OutOfResources));
+        }
+    }
+
+    #[test]
+    fn migrate_buffers() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE).unwrap();
+            let buffer = Buffer::from_slice(&context, MemFlags::READ_WRITE, &[1u32, 2, 3, 4]).unwrap();
+            let shared = buffer.clone();
+            assert_eq!(buffer.residency().unwrap().location(), Location::Unknown);
+
+            queue.enqueue_migrate(&[&buffer], MigrationFlags::HOST, &[]).unwrap().wait().unwrap();
+            assert_eq!(shared.residency().unwrap().location(), Location::Host);
+
+            let migrated = queue.enqueue_migrate(&[&buffer, &shared], MigrationFlags::NONE, &[]).unwrap();
+            assert_eq!(buffer.residency().unwrap().location(), Location::Device(queue.device()));
+
+            let mut data = [0u32; 4];
+            buffer.read(&queue, 0, &mut data, &[migrated]).unwrap();
+            assert_eq!(data, [1, 2, 3, 4]);
+
+            let program = Program::from_source(&context, &["__kernel void inc(__global uint *a) { a[get_global_id(0)]++; }"]).unwrap();
+            program.build(&[], "").unwrap();
+            let mut kernel = Kernel::new(&program, "inc").unwrap();
+            let whole = buffer.slice(..4).unwrap();
+            kernel.set_arg(0, &whole).unwrap();
+            queue.enqueue_migrate(&[&buffer], MigrationFlags::HOST, &[]).unwrap().wait().unwrap();
+            queue.enqueue_nd_range_kernel(&kernel, &NdRange::new(&[2]), &[]).unwrap().wait().unwrap();
+            assert_eq!(shared.residency().unwrap().location(), Location::Device(queue.device()));
+
+            assert_eq!(queue.enqueue_migrate(&[], MigrationFlags::NONE, &[]).err(), Some(Error::InvalidValue));
+            assert_eq!(queue.enqueue_migrate(&[&buffer], MigrationFlags::from_raw(1 << 2), &[]).err(), Some(Error::InvalidValue));
+        }
+    }
+}
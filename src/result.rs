@@ -13,6 +13,60 @@ pub enum Error {
     /// No OpenCL devices matched the queried device types.
     DeviceNotFound = -1,
 
+    /// A device is currently not available.
+    DeviceNotAvailable = -2,
+
+    /// No compiler is available for the device.
+    CompilerNotAvailable = -3,
+
+    /// There was a failure to allocate memory for a memory object.
+    MemObjectAllocationFailure = -4,
+
+    /// There was a failure to allocate resources required by the OpenCL implementation on the device.
+    OutOfResources = -5,
+
+    /// There was a failure to allocate resources required by the OpenCL implementation on the host.
+    OutOfHostMemory = -6,
+
+    /// Profiling information is not available for the event.
+    ProfilingInfoNotAvailable = -7,
+
+    /// The source and destination regions of a copy overlap.
+    MemCopyOverlap = -8,
+
+    /// The image formats of source and destination do not match.
+    ImageFormatMismatch = -9,
+
+    /// The image format is not supported.
+    ImageFormatNotSupported = -10,
+
+    /// There was a failure to build the program executable.
+    BuildProgramFailure = -11,
+
+    /// The memory object could not be mapped into the host address space.
+    MapFailure = -12,
+
+    /// The origin of a sub-buffer is not aligned to `DeviceInfo::MemBaseAddrAlign` of the devices.
+    MisalignedSubBufferOffset = -13,
+
+    /// The execution status of an event in the wait list is an error.
+    ExecStatusErrorForEventsInWaitList = -14,
+
+    /// There was a failure to compile the program source.
+    CompileProgramFailure = -15,
+
+    /// The linker is not available.
+    LinkerNotAvailable = -16,
+
+    /// There was a failure to link the compiled binaries and/or libraries.
+    LinkProgramFailure = -17,
+
+    /// The device could not be partitioned.
+    DevicePartitionFailed = -18,
+
+    /// Argument information is not available for the kernel.
+    KernelArgInfoNotAvailable = -19,
+
     /// An invalid value was passed as parameter.
     InvalidValue = -30,
 
@@ -21,6 +75,111 @@ pub enum Error {
 
     /// An invalid platform ID was passed as parameter.
     InvalidPlatform = -32,
+
+    /// An invalid device was passed as parameter.
+    InvalidDevice = -33,
+
+    /// An invalid context was passed as parameter.
+    InvalidContext = -34,
+
+    /// The specified queue properties are valid but not supported by the device.
+    InvalidQueueProperties = -35,
+
+    /// An invalid command queue was passed as parameter.
+    InvalidCommandQueue = -36,
+
+    /// An invalid host pointer was passed as parameter.
+    InvalidHostPtr = -37,
+
+    /// An invalid memory object was passed as parameter.
+    InvalidMemObject = -38,
+
+    /// An invalid image format descriptor was passed as parameter.
+    InvalidImageFormatDescriptor = -39,
+
+    /// An invalid image size was passed as parameter.
+    InvalidImageSize = -40,
+
+    /// An invalid sampler was passed as parameter.
+    InvalidSampler = -41,
+
+    /// An invalid program binary was passed for a device.
+    InvalidBinary = -42,
+
+    /// The build options are invalid.
+    InvalidBuildOptions = -43,
+
+    /// An invalid program was passed as parameter.
+    InvalidProgram = -44,
+
+    /// There is no successfully built executable for the program.
+    InvalidProgramExecutable = -45,
+
+    /// No kernel with the given name exists in the program.
+    InvalidKernelName = -46,
+
+    /// The kernel definition differs between the devices of the program.
+    InvalidKernelDefinition = -47,
+
+    /// An invalid kernel was passed as parameter.
+    InvalidKernel = -48,
+
+    /// An invalid argument index was passed as parameter.
+    InvalidArgIndex = -49,
+
+    /// An invalid argument value was passed as parameter.
+    InvalidArgValue = -50,
+
+    /// An invalid argument size was passed as parameter.
+    InvalidArgSize = -51,
+
+    /// The kernel arguments have not been specified.
+    InvalidKernelArgs = -52,
+
+    /// An invalid number of work dimensions was passed as parameter.
+    InvalidWorkDimension = -53,
+
+    /// An invalid work-group size was passed as parameter.
+    InvalidWorkGroupSize = -54,
+
+    /// An invalid work-item size was passed as parameter.
+    InvalidWorkItemSize = -55,
+
+    /// An invalid global work offset was passed as parameter.
+    InvalidGlobalOffset = -56,
+
+    /// An invalid event wait list was passed as parameter.
+    InvalidEventWaitList = -57,
+
+    /// An invalid event was passed as parameter.
+    InvalidEvent = -58,
+
+    /// The operation is not valid in the current state.
+    InvalidOperation = -59,
+
+    /// An invalid buffer size was passed as parameter.
+    InvalidBufferSize = -61,
+
+    /// An invalid global work size was passed as parameter.
+    InvalidGlobalWorkSize = -63,
+
+    /// An invalid property name or value was passed as parameter.
+    InvalidProperty = -64,
+
+    /// An invalid image descriptor was passed as parameter.
+    InvalidImageDescriptor = -65,
+
+    /// The compiler options are invalid.
+    InvalidCompilerOptions = -66,
+
+    /// The linker options are invalid.
+    InvalidLinkerOptions = -67,
+
+    /// An invalid number of sub-devices was requested.
+    InvalidDevicePartitionCount = -68,
+
+    /// An invalid pipe packet size or number of packets was passed.
+    InvalidPipeSize = -69,
 }
 
 impl Default for Error {
@@ -35,9 +194,62 @@ impl From<i32> for Error {
         match code {
             0 => Error::Success,
             -1 => Error::DeviceNotFound,
+            -2 => Error::DeviceNotAvailable,
+            -3 => Error::CompilerNotAvailable,
+            -4 => Error::MemObjectAllocationFailure,
+            -5 => Error::OutOfResources,
+            -6 => Error::OutOfHostMemory,
+            -7 => Error::ProfilingInfoNotAvailable,
+            -8 => Error::MemCopyOverlap,
+            -9 => Error::ImageFormatMismatch,
+            -10 => Error::ImageFormatNotSupported,
+            -11 => Error::BuildProgramFailure,
+            -12 => Error::MapFailure,
+            -13 => Error::MisalignedSubBufferOffset,
+            -14 => Error::ExecStatusErrorForEventsInWaitList,
+            -15 => Error::CompileProgramFailure,
+            -16 => Error::LinkerNotAvailable,
+            -17 => Error::LinkProgramFailure,
+            -18 => Error::DevicePartitionFailed,
+            -19 => Error::KernelArgInfoNotAvailable,
             -30 => Error::InvalidValue,
             -31 => Error::InvalidDeviceType,
             -32 => Error::InvalidPlatform,
+            -33 => Error::InvalidDevice,
+            -34 => Error::InvalidContext,
+            -35 => Error::InvalidQueueProperties,
+            -36 => Error::InvalidCommandQueue,
+            -37 => Error::InvalidHostPtr,
+            -38 => Error::InvalidMemObject,
+            -39 => Error::InvalidImageFormatDescriptor,
+            -40 => Error::InvalidImageSize,
+            -41 => Error::InvalidSampler,
+            -42 => Error::InvalidBinary,
+            -43 => Error::InvalidBuildOptions,
+            -44 => Error::InvalidProgram,
+            -45 => Error::InvalidProgramExecutable,
+            -46 => Error::InvalidKernelName,
+            -47 => Error::InvalidKernelDefinition,
+            -48 => Error::InvalidKernel,
+            -49 => Error::InvalidArgIndex,
+            -50 => Error::InvalidArgValue,
+            -51 => Error::InvalidArgSize,
+            -52 => Error::InvalidKernelArgs,
+            -53 => Error::InvalidWorkDimension,
+            -54 => Error::InvalidWorkGroupSize,
+            -55 => Error::InvalidWorkItemSize,
+            -56 => Error::InvalidGlobalOffset,
+            -57 => Error::InvalidEventWaitList,
+            -58 => Error::InvalidEvent,
+            -59 => Error::InvalidOperation,
+            -61 => Error::InvalidBufferSize,
+            -63 => Error::InvalidGlobalWorkSize,
+            -64 => Error::InvalidProperty,
+            -65 => Error::InvalidImageDescriptor,
+            -66 => Error::InvalidCompilerOptions,
+            -67 => Error::InvalidLinkerOptions,
+            -68 => Error::InvalidDevicePartitionCount,
+            -69 => Error::InvalidPipeSize,
             _ => Error::Unknown,
         }
     }
@@ -49,13 +261,68 @@ impl Display for Error {
             Error::Unknown => write!(f, "An unknown error occurred."),
             Error::Success => write!(f, "The operation completed successfully."),
             Error::DeviceNotFound => write!(f, "No OpenCL devices matched the queried device types."),
+            Error::DeviceNotAvailable => write!(f, "A device is currently not available."),
+            Error::CompilerNotAvailable => write!(f, "No compiler is available for the device."),
+            Error::MemObjectAllocationFailure => write!(f, "There was a failure to allocate memory for a memory object."),
+            Error::OutOfResources => write!(f, "There was a failure to allocate resources on the device."),
+            Error::OutOfHostMemory => write!(f, "There was a failure to allocate resources on the host."),
+            Error::ProfilingInfoNotAvailable => write!(f, "Profiling information is not available for the event."),
+            Error::MemCopyOverlap => write!(f, "The source and destination regions of a copy overlap."),
+            Error::ImageFormatMismatch => write!(f, "The image formats of source and destination do not match."),
+            Error::ImageFormatNotSupported => write!(f, "The image format is not supported."),
+            Error::BuildProgramFailure => write!(f, "There was a failure to build the program executable."),
+            Error::MapFailure => write!(f, "The memory object could not be mapped into the host address space."),
+            Error::MisalignedSubBufferOffset => write!(f, "The origin of the sub-buffer is not aligned to the base address alignment of the devices."),
+            Error::ExecStatusErrorForEventsInWaitList => write!(f, "The execution status of an event in the wait list is an error."),
+            Error::CompileProgramFailure => write!(f, "There was a failure to compile the program source."),
+            Error::LinkerNotAvailable => write!(f, "The linker is not available."),
+            Error::LinkProgramFailure => write!(f, "There was a failure to link the compiled binaries and/or libraries."),
+            Error::DevicePartitionFailed => write!(f, "The device could not be partitioned."),
+            Error::KernelArgInfoNotAvailable => write!(f, "Argument information is not available for the kernel."),
             Error::InvalidValue => write!(f, "An invalid value was passed as parameter."),
             Error::InvalidDeviceType => write!(f, "An invalid device type was passed as parameter."),
             Error::InvalidPlatform => write!(f, "An invalid platform ID was passed as parameter."),
+            Error::InvalidDevice => write!(f, "An invalid device was passed as parameter."),
+            Error::InvalidContext => write!(f, "An invalid context was passed as parameter."),
+            Error::InvalidQueueProperties => write!(f, "The specified queue properties are not supported by the device."),
+            Error::InvalidCommandQueue => write!(f, "An invalid command queue was passed as parameter."),
+            Error::InvalidHostPtr => write!(f, "An invalid host pointer was passed as parameter."),
+            Error::InvalidMemObject => write!(f, "An invalid memory object was passed as parameter."),
+            Error::InvalidImageFormatDescriptor => write!(f, "An invalid image format descriptor was passed as parameter."),
+            Error::InvalidImageSize => write!(f, "An invalid image size was passed as parameter."),
+            Error::InvalidSampler => write!(f, "An invalid sampler was passed as parameter."),
+            Error::InvalidBinary => write!(f, "An invalid program binary was passed for a device."),
+            Error::InvalidBuildOptions => write!(f, "The build options are invalid."),
+            Error::InvalidProgram => write!(f, "An invalid program was passed as parameter."),
+            Error::InvalidProgramExecutable => write!(f, "There is no successfully built executable for the program."),
+            Error::InvalidKernelName => write!(f, "No kernel with the given name exists in the program."),
+            Error::InvalidKernelDefinition => write!(f, "The kernel definition differs between the devices of the program."),
+            Error::InvalidKernel => write!(f, "An invalid kernel was passed as parameter."),
+            Error::InvalidArgIndex => write!(f, "An invalid argument index was passed as parameter."),
+            Error::InvalidArgValue => write!(f, "An invalid argument value was passed as parameter."),
+            Error::InvalidArgSize => write!(f, "An invalid argument size was passed as parameter."),
+            Error::InvalidKernelArgs => write!(f, "The kernel arguments have not been specified."),
+            Error::InvalidWorkDimension => write!(f, "An invalid number of work dimensions was passed as parameter."),
+            Error::InvalidWorkGroupSize => write!(f, "An invalid work-group size was passed as parameter."),
+            Error::InvalidWorkItemSize => write!(f, "An invalid work-item size was passed as parameter."),
+            Error::InvalidGlobalOffset => write!(f, "An invalid global work offset was passed as parameter."),
+            Error::InvalidEventWaitList => write!(f, "An invalid event wait list was passed as parameter."),
+            Error::InvalidEvent => write!(f, "An invalid event was passed as parameter."),
+            Error::InvalidOperation => write!(f, "The operation is not valid in the current state."),
+            Error::InvalidBufferSize => write!(f, "An invalid buffer size was passed as parameter."),
+            Error::InvalidGlobalWorkSize => write!(f, "An invalid global work size was passed as parameter."),
+            Error::InvalidProperty => write!(f, "An invalid property name or value was passed as parameter."),
+            Error::InvalidImageDescriptor => write!(f, "An invalid image descriptor was passed as parameter."),
+            Error::InvalidCompilerOptions => write!(f, "The compiler options are invalid."),
+            Error::InvalidLinkerOptions => write!(f, "The linker options are invalid."),
+            Error::InvalidDevicePartitionCount => write!(f, "An invalid number of sub-devices was requested."),
+            Error::InvalidPipeSize => write!(f, "An invalid pipe packet size or number of packets was passed."),
         }
     }
 }
 
+impl std::error::Error for Error {}
+
 /// The result type for OpenCL operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -67,4 +334,12 @@ mod tests {
     fn default_error_code() {
         assert_eq!(Error::default(), Error::Unknown);
     }
+
+    #[test]
+    fn error_code_round_trip() {
+        for code in [0, -1, -2, -3, -4, -5, -6, -7, -8, -9, -10, -11, -12, -13, -14, -15, -16, -17, -18, -19, -30, -31, -32, -33, -34, -35, -36, -37, -38, -39, -40, -41, -42, -43, -44, -45, -46, -47, -48, -49, -50, -51, -52, -53, -54, -55, -56, -57, -58, -59, -61, -63, -64, -65, -66, -67, -68, -69] {
+            assert_eq!(Error::from(code) as i32, code);
+        }
+        assert_eq!(Error::from(-1000), Error::Unknown);
+    }
 }
@@ -0,0 +1,198 @@
+use std::ffi::c_void;
+use crate::context::Context;
+use crate::info;
+use crate::kernel::{ArgInfo, KernelArg};
+use crate::native::{clCreateSampler, clCreateSamplerWithProperties, clGetSamplerInfo, clReleaseSampler, clRetainSampler};
+use crate::platform::Platform;
+use crate::result::{Error, Result};
+use crate::types::{AddressingMode, FilterMode, SamplerId, SamplerInfo, SamplerProperty, SAMPLER_ADDRESSING_MODE, SAMPLER_FILTER_MODE, SAMPLER_NORMALIZED_COORDS};
+
+/// Get a sampler info for the given sampler.
+///
+/// # Safety
+///
+/// The caller must make sure the generic type parameter matches the type of the sampler info.
+pub unsafe fn cl_get_sampler_info<T: Sized>(sampler: SamplerId, name: SamplerInfo) -> Result<T> {
+    info::get_value(|size, value, size_ret| clGetSamplerInfo(sampler, name, size, value, size_ret))
+}
+
+/// A sampler describing how a kernel reads an image.
+///
+/// The sampler is retained when cloned and released when dropped.
+pub struct Sampler {
+    id: SamplerId,
+    context: Context,
+}
+
+impl Sampler {
+    /// Create a sampler in the given context.
+    ///
+    /// On platforms supporting OpenCL 2.0 or newer `clCreateSamplerWithProperties` is used if the loaded library exports it,
+    /// otherwise the sampler is created with the deprecated `clCreateSampler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the sampler is created in.
+    /// * `normalized_coords` - Whether image coordinates are normalized to `[0, 1]`.
+    /// * `addressing_mode` - How out of range image coordinates are handled.
+    /// * `filter_mode` - How image elements are filtered.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidContext` - An invalid context was passed.
+    /// * `Error::InvalidValue` - The combination of settings is invalid,
+    ///   e.g. `AddressingMode::Repeat` without normalized coordinates.
+    /// * `Error::InvalidOperation` - No device in the context supports images.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::sampler::Sampler;
+    /// # use rusty_cl::types::{AddressingMode, DeviceType, FilterMode};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let sampler = Sampler::new(&context, true, AddressingMode::Repeat, FilterMode::Linear)?;
+    /// assert_eq!(sampler.filter_mode()?, FilterMode::Linear);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(context: &Context, normalized_coords: bool, addressing_mode: AddressingMode, filter_mode: FilterMode) -> Result<Self> {
+        let version = Platform::get(context.platform())?.version().major();
+
+        let mut result: i32 = 0;
+        let id = if let Some(create) = clCreateSamplerWithProperties().filter(|_| version >= 2) {
+            let properties: [SamplerProperty; 7] = [
+                SAMPLER_NORMALIZED_COORDS, normalized_coords as SamplerProperty,
+                SAMPLER_ADDRESSING_MODE, addressing_mode as SamplerProperty,
+                SAMPLER_FILTER_MODE, filter_mode as SamplerProperty,
+                0,
+            ];
+            unsafe { create(context.id(), properties.as_ptr(), &mut result) }
+        } else {
+            unsafe { clCreateSampler(context.id(), normalized_coords as u32, addressing_mode as u32, filter_mode as u32, &mut result) }
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+
+        Ok(Self { id, context: context.clone() })
+    }
+
+    /// The native handle of the sampler.
+    pub fn id(&self) -> SamplerId {
+        self.id
+    }
+
+    /// The context the sampler was created in.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Whether the sampler uses normalized coordinates.
+    pub fn normalized_coords(&self) -> Result<bool> {
+        Ok(unsafe { cl_get_sampler_info::<u32>(self.id, SamplerInfo::NormalizedCoords) }? != 0)
+    }
+
+    /// The addressing mode of the sampler.
+    pub fn addressing_mode(&self) -> Result<AddressingMode> {
+        let mode = unsafe { cl_get_sampler_info::<u32>(self.id, SamplerInfo::AddressingMode) }?;
+        AddressingMode::try_from(mode).map_err(|_| Error::InvalidValue)
+    }
+
+    /// The filter mode of the sampler.
+    pub fn filter_mode(&self) -> Result<FilterMode> {
+        let mode = unsafe { cl_get_sampler_info::<u32>(self.id, SamplerInfo::FilterMode) }?;
+        FilterMode::try_from(mode).map_err(|_| Error::InvalidValue)
+    }
+
+    /// The current reference count of the sampler.
+    ///
+    /// # Remarks
+    ///
+    /// The reference count should be considered immediately stale.
+    /// It is only useful for identifying memory leaks.
+    pub fn reference_count(&self) -> Result<u32> {
+        unsafe { cl_get_sampler_info(self.id, SamplerInfo::ReferenceCount) }
+    }
+}
+
+unsafe impl KernelArg for Sampler {
+    fn size(&self) -> usize {
+        std::mem::size_of::<SamplerId>()
+    }
+
+    fn value(&self) -> *const c_void {
+        &self.id as *const SamplerId as *const c_void
+    }
+
+    fn matches(&self, info: &ArgInfo) -> bool {
+        !info.is_pointer() && info.type_name() == "sampler_t"
+    }
+
+    fn describe(&self) -> String {
+        "a `sampler_t`".to_string()
+    }
+
+    fn is_sampler(&self) -> bool {
+        true
+    }
+}
+
+impl Clone for Sampler {
+    fn clone(&self) -> Self {
+        let result = unsafe { clRetainSampler(self.id) };
+        debug_assert_eq!(result, 0);
+
+        Self { id: self.id, context: self.context.clone() }
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        let result = unsafe { clReleaseSampler(self.id) };
+        debug_assert_eq!(result, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::cl_get_platform_ids;
+    use crate::types::{ContextId, DeviceType};
+
+    #[test]
+    fn create_samplers() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+
+            let sampler = match Sampler::new(&context, true, AddressingMode::Repeat, FilterMode::Linear) {
+                Ok(sampler) => sampler,
+                Err(_) => continue,
+            };
+            assert!(sampler.normalized_coords().unwrap());
+            assert_eq!(sampler.addressing_mode().unwrap(), AddressingMode::Repeat);
+            assert_eq!(sampler.filter_mode().unwrap(), FilterMode::Linear);
+            assert_eq!(unsafe { cl_get_sampler_info::<ContextId>(sampler.id(), SamplerInfo::Context) }.unwrap(), context.id());
+
+            let sampler = Sampler::new(&context, false, AddressingMode::ClampToEdge, FilterMode::Nearest).unwrap();
+            assert!(!sampler.normalized_coords().unwrap());
+            assert_eq!(sampler.addressing_mode().unwrap(), AddressingMode::ClampToEdge);
+            assert_eq!(sampler.filter_mode().unwrap(), FilterMode::Nearest);
+        }
+    }
+
+    #[test]
+    fn decode_modes() {
+        assert_eq!(AddressingMode::try_from(0x1134), Ok(AddressingMode::MirroredRepeat));
+        assert_eq!(AddressingMode::try_from(0x1135), Err(0x1135));
+        assert_eq!(FilterMode::try_from(0x1140), Ok(FilterMode::Nearest));
+        assert_eq!(FilterMode::try_from(0x1142), Err(0x1142));
+    }
+}
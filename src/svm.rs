@@ -0,0 +1,532 @@
+use std::ffi::c_void;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use crate::context::Context;
+use crate::device::cl_get_device_info;
+use crate::event::{self, Event};
+use crate::kernel::{ArgInfo, KernelArg};
+use crate::memory::check_write_flags;
+use crate::native::{clEnqueueSVMMap, clEnqueueSVMMemFill, clEnqueueSVMMemcpy, clEnqueueSVMUnmap, clSVMAlloc, clSVMFree};
+use crate::queue::CommandQueue;
+use crate::result::{Error, Result};
+use crate::types::{AddressQualifier, DeviceId, DeviceInfo, EventId, MapFlags, MemFlags, Pod, SvmCapabilities};
+
+/// Get the shared virtual memory capabilities of the given device.
+///
+/// Devices not supporting OpenCL 2.0 report no capabilities.
+///
+/// # Errors
+///
+/// The following errors may be returned:
+///
+/// * `Error::InvalidDevice` - An invalid device was passed.
+pub fn svm_capabilities(device: DeviceId) -> Result<SvmCapabilities> {
+    match unsafe { cl_get_device_info::<u64>(device, DeviceInfo::SvmCapabilities) } {
+        Ok(capabilities) => Ok(SvmCapabilities::from_raw(capabilities)),
+        Err(Error::InvalidValue) => Ok(SvmCapabilities::NONE),
+        Err(error) => Err(error),
+    }
+}
+
+/// The capabilities all devices need to allocate shared virtual memory with the given flags.
+fn required_capabilities(flags: MemFlags) -> SvmCapabilities {
+    let mut required = if flags.contains(MemFlags::SVM_FINE_GRAIN_BUFFER) {
+        SvmCapabilities::FINE_GRAIN_BUFFER
+    } else {
+        SvmCapabilities::COARSE_GRAIN_BUFFER
+    };
+    if flags.contains(MemFlags::SVM_ATOMICS) {
+        required = required | SvmCapabilities::ATOMICS;
+    }
+    required
+}
+
+/// A shared virtual memory allocation, shared by an [SvmVec] and the kernels it is passed to as an argument.
+///
+/// The memory is freed once the last of them is dropped. Before that, the queues which enqueued commands
+/// using the memory are finished, so that commands which have not completed yet, e.g. fills or kernels,
+/// do not access freed memory.
+pub struct SvmAllocation {
+    // Stored as an address so that the allocation can be shared between threads like the context.
+    ptr: usize,
+    context: Context,
+    queues: Mutex<Vec<CommandQueue>>,
+}
+
+impl SvmAllocation {
+    /// Record that a command on the given queue uses the memory.
+    pub(crate) fn used_by(&self, queue: &CommandQueue) {
+        let mut queues = self.queues.lock().unwrap();
+        if !queues.iter().any(|used| used.id() == queue.id()) {
+            queues.push(queue.clone());
+        }
+    }
+}
+
+impl Drop for SvmAllocation {
+    fn drop(&mut self) {
+        // clSVMFree does not wait for enqueued commands using the memory.
+        for queue in self.queues.get_mut().unwrap().iter() {
+            let result = queue.finish();
+            debug_assert!(result.is_ok());
+        }
+        if let Some(free) = clSVMFree() {
+            unsafe { free(self.context.id(), self.ptr as *mut c_void) };
+        }
+    }
+}
+
+/// A typed shared virtual memory allocation holding `len` elements of type `T`.
+///
+/// Unlike buffers, the allocation has the same address on the host and the devices of its context,
+/// so pointers into it may be stored inside the allocation itself, e.g. to build linked data structures.
+/// Coarse-grained allocations must be mapped with [SvmVec::map] before the host accesses them,
+/// fine-grained allocations (`MemFlags::SVM_FINE_GRAIN_BUFFER`) can be accessed directly.
+///
+/// The allocation is freed when dropped and no kernel it was passed to as an argument exists anymore,
+/// after all queues which used it have been finished.
+pub struct SvmVec<T: Pod> {
+    ptr: *mut T,
+    len: usize,
+    flags: MemFlags,
+    allocation: Arc<SvmAllocation>,
+}
+
+impl<T: Pod> SvmVec<T> {
+    /// Allocate uninitialized shared virtual memory for `len` elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context the memory is shared with.
+    /// * `flags` - How the memory is accessed by kernels, optionally with `SVM_FINE_GRAIN_BUFFER` and `SVM_ATOMICS`.
+    /// * `len` - The number of elements.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags contain host pointer or host access flags.
+    /// * `Error::InvalidBufferSize` - The allocation would be empty.
+    /// * `Error::InvalidOperation` - A device of the context does not have the `SvmCapabilities` required by the flags,
+    ///   or the OpenCL library does not support shared virtual memory.
+    /// * `Error::MemObjectAllocationFailure` - The memory could not be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rusty_cl::context::Context;
+    /// # use rusty_cl::platform::Platform;
+    /// # use rusty_cl::queue::CommandQueue;
+    /// # use rusty_cl::svm::SvmVec;
+    /// # use rusty_cl::types::{CommandQueueProperties, DeviceType, MapFlags, MemFlags};
+    /// # fn main() -> rusty_cl::result::Result<()> {
+    /// let context = Context::from_type(Platform::default().id(), DeviceType::GPU)?;
+    /// let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE)?;
+    /// let mut next = SvmVec::<usize>::new(&context, MemFlags::READ_WRITE, 16)?;
+    /// let base = next.as_ptr() as usize;
+    /// let mut nodes = next.map(&queue, MapFlags::WRITE_INVALIDATE_REGION, &[])?;
+    /// for (i, node) in nodes.iter_mut().enumerate() {
+    ///     *node = base + ((i + 1) % 16) * std::mem::size_of::<usize>();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(context: &Context, flags: MemFlags, len: usize) -> Result<Self> {
+        let host_flags = MemFlags::USE_HOST_PTR | MemFlags::ALLOC_HOST_PTR | MemFlags::COPY_HOST_PTR
+            | MemFlags::HOST_WRITE_ONLY | MemFlags::HOST_READ_ONLY | MemFlags::HOST_NO_ACCESS;
+        if flags & host_flags != MemFlags::NONE {
+            return Err(Error::InvalidValue);
+        }
+        let size = len.checked_mul(std::mem::size_of::<T>()).ok_or(Error::InvalidBufferSize)?;
+        if size == 0 {
+            return Err(Error::InvalidBufferSize);
+        }
+
+        let required = required_capabilities(flags);
+        for &device in context.devices() {
+            if !svm_capabilities(device)?.contains(required) {
+                return Err(Error::InvalidOperation);
+            }
+        }
+
+        let alloc = clSVMAlloc().ok_or(Error::InvalidOperation)?;
+        let ptr = unsafe { alloc(context.id(), flags.raw(), size, std::mem::align_of::<T>() as u32) } as *mut T;
+        if ptr.is_null() {
+            return Err(Error::MemObjectAllocationFailure);
+        }
+
+        let allocation = SvmAllocation { ptr: ptr as usize, context: context.clone(), queues: Mutex::new(vec![]) };
+        Ok(Self { ptr, len, flags, allocation: Arc::new(allocation) })
+    }
+
+    /// The context the memory is shared with.
+    pub fn context(&self) -> &Context {
+        &self.allocation.context
+    }
+
+    /// The flags the memory was allocated with.
+    pub fn flags(&self) -> MemFlags {
+        self.flags
+    }
+
+    /// Whether the host can access the memory without mapping it.
+    pub fn is_fine_grained(&self) -> bool {
+        self.flags.contains(MemFlags::SVM_FINE_GRAIN_BUFFER)
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no elements.
+    ///
+    /// This is always `false`, as empty allocations are rejected.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The size of the allocation in bytes.
+    pub fn size(&self) -> usize {
+        self.len * std::mem::size_of::<T>()
+    }
+
+    /// The address of the first element, which is valid on the host and on the devices.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// The mutable address of the first element, which is valid on the host and on the devices.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    /// Access a fine-grained allocation from the host, `None` for coarse-grained allocations.
+    ///
+    /// # Safety
+    ///
+    /// No kernel writing to the memory may be running while the slice is alive.
+    pub unsafe fn as_slice(&self) -> Option<&[T]> {
+        self.is_fine_grained().then(|| std::slice::from_raw_parts(self.ptr, self.len))
+    }
+
+    /// Mutably access a fine-grained allocation from the host, `None` for coarse-grained allocations.
+    ///
+    /// # Safety
+    ///
+    /// No kernel accessing the memory may be running while the slice is alive.
+    pub unsafe fn as_mut_slice(&mut self) -> Option<&mut [T]> {
+        self.is_fine_grained().then(|| std::slice::from_raw_parts_mut(self.ptr, self.len))
+    }
+
+    fn check_range(&self, offset: usize, len: usize) -> Result<()> {
+        match offset.checked_add(len) {
+            Some(end) if len > 0 && end <= self.len => Ok(()),
+            _ => Err(Error::InvalidValue),
+        }
+    }
+
+    /// Map the memory for host writes, blocking until it is mapped.
+    ///
+    /// The memory is unmapped when the returned guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The flags do not request write access.
+    /// * `Error::InvalidContext` - The queue and the memory belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn map<'a>(&'a mut self, queue: &'a CommandQueue, flags: MapFlags, wait_list: &[Event]) -> Result<SvmMapGuard<'a, T>> {
+        check_write_flags(flags)?;
+        self.enqueue_map(queue, flags, wait_list)
+    }
+
+    /// Map the memory for host reads, blocking until it is mapped.
+    ///
+    /// The memory is unmapped when the returned guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidContext` - The queue and the memory belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn map_read<'a>(&'a mut self, queue: &'a CommandQueue, wait_list: &[Event]) -> Result<SvmMapReadGuard<'a, T>> {
+        Ok(SvmMapReadGuard(self.enqueue_map(queue, MapFlags::READ, wait_list)?))
+    }
+
+    fn enqueue_map<'a>(&'a mut self, queue: &'a CommandQueue, flags: MapFlags, wait_list: &[Event]) -> Result<SvmMapGuard<'a, T>> {
+        let map = clEnqueueSVMMap().ok_or(Error::InvalidOperation)?;
+        let (num_events, events) = event::wait_list(wait_list);
+        let result = unsafe {
+            map(queue.id(), 1, flags.raw(), self.ptr as *mut c_void, self.size(), num_events, events, std::ptr::null_mut())
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.allocation.used_by(queue);
+        Ok(SvmMapGuard { svm: self, queue })
+    }
+
+    /// Read elements starting at `offset` into `data`, blocking until done.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region being read is empty or out of bounds.
+    /// * `Error::InvalidContext` - The queue and the memory belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn read(&self, queue: &CommandQueue, offset: usize, data: &mut [T], wait_list: &[Event]) -> Result<Event> {
+        self.check_range(offset, data.len())?;
+        unsafe {
+            enqueue_memcpy(queue, data.as_mut_ptr() as *mut c_void, self.ptr.add(offset) as *const c_void, std::mem::size_of_val(data), wait_list)
+        }
+    }
+
+    /// Write `data` starting at `offset`, blocking until done.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region being written is empty or out of bounds.
+    /// * `Error::InvalidContext` - The queue and the memory belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn write(&mut self, queue: &CommandQueue, offset: usize, data: &[T], wait_list: &[Event]) -> Result<Event> {
+        self.check_range(offset, data.len())?;
+        unsafe {
+            enqueue_memcpy(queue, self.ptr.add(offset) as *mut c_void, data.as_ptr() as *const c_void, std::mem::size_of_val(data), wait_list)
+        }
+    }
+
+    /// Copy `len` elements from this allocation to `dst`, blocking until done.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - One of the regions is empty or out of bounds.
+    /// * `Error::InvalidContext` - The queue and the memory belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn copy_to(&self, queue: &CommandQueue, dst: &mut SvmVec<T>, src_offset: usize, dst_offset: usize, len: usize, wait_list: &[Event]) -> Result<Event> {
+        self.check_range(src_offset, len)?;
+        dst.check_range(dst_offset, len)?;
+        unsafe {
+            enqueue_memcpy(
+                queue, dst.ptr.add(dst_offset) as *mut c_void, self.ptr.add(src_offset) as *const c_void, len * std::mem::size_of::<T>(), wait_list,
+            )
+        }
+    }
+
+    /// Enqueue filling `len` elements starting at `offset` with `value`.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::InvalidValue` - The region is empty or out of bounds,
+    ///   or the size of `T` is not a power of two of at most 128 bytes.
+    /// * `Error::InvalidContext` - The queue and the memory belong to different contexts.
+    /// * `Error::ExecStatusErrorForEventsInWaitList` - A command in the wait list terminated abnormally.
+    pub fn fill(&mut self, queue: &CommandQueue, value: T, offset: usize, len: usize, wait_list: &[Event]) -> Result<Event> {
+        let pattern_size = std::mem::size_of::<T>();
+        if !pattern_size.is_power_of_two() || pattern_size > 128 {
+            return Err(Error::InvalidValue);
+        }
+        self.check_range(offset, len)?;
+
+        let fill = clEnqueueSVMMemFill().ok_or(Error::InvalidOperation)?;
+        let (num_events, events) = event::wait_list(wait_list);
+        let mut event: EventId = 0;
+        let result = unsafe {
+            fill(
+                queue.id(), self.ptr.add(offset) as *mut c_void, &value as *const T as *const c_void, pattern_size, len * pattern_size,
+                num_events, events, &mut event,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        self.allocation.used_by(queue);
+        Ok(Event::from_id(event))
+    }
+}
+
+/// Enqueue a blocking copy between shared virtual memory and host memory.
+unsafe fn enqueue_memcpy(queue: &CommandQueue, dst: *mut c_void, src: *const c_void, size: usize, wait_list: &[Event]) -> Result<Event> {
+    let memcpy = clEnqueueSVMMemcpy().ok_or(Error::InvalidOperation)?;
+    let (num_events, events) = event::wait_list(wait_list);
+    let mut event: EventId = 0;
+    let result = memcpy(queue.id(), 1, dst, src, size, num_events, events, &mut event);
+    if result != 0 {
+        return Err(Error::from(result));
+    }
+    Ok(Event::from_id(event))
+}
+
+unsafe impl<T: Pod> KernelArg for SvmVec<T> {
+    fn size(&self) -> usize {
+        std::mem::size_of::<*const T>()
+    }
+
+    fn value(&self) -> *const c_void {
+        self.ptr as *const c_void
+    }
+
+    fn matches(&self, info: &ArgInfo) -> bool {
+        matches!(info.address_qualifier(), AddressQualifier::Global | AddressQualifier::Constant)
+            && info.is_pointer()
+            && info.type_matches(T::cl_type_name())
+    }
+
+    fn describe(&self) -> String {
+        match T::cl_type_name() {
+            Some(name) => format!("shared virtual memory of `{}`", name),
+            None => format!("shared virtual memory of {} byte elements", std::mem::size_of::<T>()),
+        }
+    }
+
+    fn svm_pointer(&self) -> Option<*const c_void> {
+        Some(self.ptr as *const c_void)
+    }
+
+    fn svm_allocation(&self) -> Option<&Arc<SvmAllocation>> {
+        Some(&self.allocation)
+    }
+}
+
+/// Host access to shared virtual memory mapped for writing, unmapped when dropped.
+pub struct SvmMapGuard<'a, T: Pod> {
+    svm: &'a mut SvmVec<T>,
+    queue: &'a CommandQueue,
+}
+
+impl<T: Pod> SvmMapGuard<'_, T> {
+    /// Unmap the memory, returning the event of the unmap command.
+    ///
+    /// Dropping the guard unmaps the memory as well, but waits for the unmap to complete and ignores errors.
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::OutOfResources` - The unmap could not be enqueued.
+    pub fn unmap(self) -> Result<Event> {
+        let result = self.enqueue_unmap();
+        std::mem::forget(self);
+        result
+    }
+
+    fn enqueue_unmap(&self) -> Result<Event> {
+        let unmap = clEnqueueSVMUnmap().ok_or(Error::InvalidOperation)?;
+        let mut event: EventId = 0;
+        let result = unsafe { unmap(self.queue.id(), self.svm.ptr as *mut c_void, 0, std::ptr::null(), &mut event) };
+        if result != 0 {
+            return Err(Error::from(result));
+        }
+        Ok(Event::from_id(event))
+    }
+}
+
+impl<T: Pod> Deref for SvmMapGuard<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::slice::from_raw_parts(self.svm.ptr, self.svm.len) }
+    }
+}
+
+impl<T: Pod> DerefMut for SvmMapGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { std::slice::from_raw_parts_mut(self.svm.ptr, self.svm.len) }
+    }
+}
+
+/// Read-only host access to mapped shared virtual memory, unmapped when dropped.
+pub struct SvmMapReadGuard<'a, T: Pod>(SvmMapGuard<'a, T>);
+
+impl<T: Pod> SvmMapReadGuard<'_, T> {
+    /// Unmap the memory, returning the event of the unmap command, see [SvmMapGuard::unmap].
+    ///
+    /// # Errors
+    ///
+    /// The following errors may be returned:
+    ///
+    /// * `Error::OutOfResources` - The unmap could not be enqueued.
+    pub fn unmap(self) -> Result<Event> {
+        self.0.unmap()
+    }
+}
+
+impl<T: Pod> Deref for SvmMapReadGuard<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Pod> Drop for SvmMapGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Ok(event) = self.enqueue_unmap() {
+            let result = event.wait();
+            debug_assert!(result.is_ok());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::cl_get_platform_ids;
+    use crate::types::{CommandQueueProperties, DeviceType};
+
+    #[test]
+    fn required_svm_capabilities() {
+        assert_eq!(required_capabilities(MemFlags::READ_WRITE), SvmCapabilities::COARSE_GRAIN_BUFFER);
+        assert_eq!(required_capabilities(MemFlags::SVM_FINE_GRAIN_BUFFER), SvmCapabilities::FINE_GRAIN_BUFFER);
+        assert_eq!(
+            required_capabilities(MemFlags::SVM_FINE_GRAIN_BUFFER | MemFlags::SVM_ATOMICS),
+            SvmCapabilities::FINE_GRAIN_BUFFER | SvmCapabilities::ATOMICS,
+        );
+    }
+
+    #[test]
+    fn shared_memory() {
+        for platform in cl_get_platform_ids() {
+            let context = match Context::from_type(platform, DeviceType::ALL) {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+            let queue = CommandQueue::new(&context, context.devices()[0], CommandQueueProperties::NONE).unwrap();
+
+            let mut svm = match SvmVec::<u32>::new(&context, MemFlags::READ_WRITE, 8) {
+                Ok(svm) => svm,
+                Err(Error::InvalidOperation) => continue,
+                Err(error) => panic!("{}", error),
+            };
+            assert_eq!(svm.size(), 32);
+            assert!(unsafe { svm.as_slice() }.is_none());
+
+            svm.fill(&queue, 7, 0, 8, &[]).unwrap();
+            svm.write(&queue, 2, &[1, 2], &[]).unwrap();
+            {
+                let mut mapped = svm.map(&queue, MapFlags::READ | MapFlags::WRITE, &[]).unwrap();
+                assert_eq!(*mapped, [7, 7, 1, 2, 7, 7, 7, 7]);
+                mapped[7] = 3;
+            }
+            assert_eq!(svm.map_read(&queue, &[]).unwrap()[7], 3);
+            assert_eq!(svm.map(&queue, MapFlags::READ, &[]).err(), Some(Error::InvalidValue));
+
+            let mut data = [0u32; 3];
+            svm.read(&queue, 5, &mut data, &[]).unwrap();
+            assert_eq!(data, [7, 7, 3]);
+
+            assert_eq!(svm.read(&queue, 6, &mut data, &[]).err(), Some(Error::InvalidValue));
+            assert_eq!(SvmVec::<u32>::new(&context, MemFlags::READ_WRITE, 0).err(), Some(Error::InvalidBufferSize));
+            assert_eq!(SvmVec::<u32>::new(&context, MemFlags::USE_HOST_PTR, 4).err(), Some(Error::InvalidValue));
+        }
+    }
+}
@@ -8,6 +8,81 @@ pub type PlatformId = usize;
 /// cl_device_id
 pub type DeviceId = usize;
 
+/// cl_context
+pub type ContextId = usize;
+
+/// cl_context_properties
+pub type ContextProperty = isize;
+
+/// cl_command_queue
+pub type CommandQueueId = usize;
+
+/// cl_queue_properties
+pub type QueueProperty = u64;
+
+/// cl_mem
+pub type MemId = usize;
+
+/// cl_event
+pub type EventId = usize;
+
+/// cl_program
+pub type ProgramId = usize;
+
+/// cl_kernel
+pub type KernelId = usize;
+
+/// cl_device_partition_property
+pub type PartitionProperty = isize;
+
+/// cl_sampler
+pub type SamplerId = usize;
+
+/// cl_sampler_properties
+pub type SamplerProperty = u64;
+
+/// Marker for plain-old-data types which may be transferred between host and device memory.
+///
+/// # Safety
+///
+/// Implementors must be `Copy`, must not contain any pointers or references,
+/// and every bit pattern of the size of the type must be a valid value.
+pub unsafe trait Pod: Copy + Send + Sync + 'static {
+    /// The name of the corresponding OpenCL C type, if there is one.
+    fn cl_type_name() -> Option<String> {
+        None
+    }
+}
+
+macro_rules! pod {
+    ($($t:ty => $name:expr),*) => {
+        $(unsafe impl Pod for $t {
+            fn cl_type_name() -> Option<String> {
+                $name.map(String::from)
+            }
+        })*
+    };
+}
+
+pod!(
+    u8 => Some("uchar"), i8 => Some("char"), u16 => Some("ushort"), i16 => Some("short"),
+    u32 => Some("uint"), i32 => Some("int"), u64 => Some("ulong"), i64 => Some("long"),
+    usize => None::<&str>, isize => None::<&str>, f32 => Some("float"), f64 => Some("double")
+);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {
+    /// Arrays of 2, 4, 8 or 16 scalars correspond to the OpenCL C vector types, e.g. `[f32; 4]` is `float4`.
+    ///
+    /// 3-component vectors have the size of 4-component vectors, so there is no array corresponding to e.g. `float3`.
+    fn cl_type_name() -> Option<String> {
+        let scalar = T::cl_type_name()?;
+        if !matches!(N, 2 | 4 | 8 | 16) || scalar.ends_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        Some(format!("{}{}", scalar, N))
+    }
+}
+
 /// cl_platform_info
 #[derive(Eq, PartialEq, Copy, Clone)]
 #[cfg_attr(test, derive(Debug))]
@@ -161,8 +236,817 @@ pub enum DeviceInfo {
     ReferenceCount = 0x1047,
     PreferredInteropUserSync = 0x1048,
     PrintfBufferSize = 0x1049,
+
+    /// The shared virtual memory capabilities of the device as `SvmCapabilities` bit-field (OpenCL 2.0).
+    SvmCapabilities = 0x1053,
+
+    /// The maximum number of pipe objects that can be passed as arguments to a kernel (OpenCL 2.0).
+    MaxPipeArgs = 0x1055,
+
+    /// The maximum number of reservations that can be active for a pipe per work-item in a kernel (OpenCL 2.0).
+    PipeMaxActiveReservations = 0x1056,
+
+    /// The maximum size of a pipe packet in bytes (OpenCL 2.0).
+    PipeMaxPacketSize = 0x1057,
+
+    /// A space separated list of the intermediate languages supported by the device, e.g. `SPIR-V_1.0` (OpenCL 2.1).
+    IlVersion = 0x105B,
+
+    /// An array of `cl_name_version` describing the intermediate languages supported by the device (OpenCL 3.0).
+    IlsWithVersion = 0x1061,
+
+    /// Whether the device supports non-uniform work-groups (OpenCL 3.0).
+    NonUniformWorkGroupSupport = 0x1065,
+}
+
+/// cl_context_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum ContextInfo {
+    /// The context reference count.
+    ReferenceCount = 0x1080,
+    /// The list of devices in the context.
+    Devices = 0x1081,
+    /// The properties argument specified when the context was created.
+    Properties = 0x1082,
+    /// The number of devices in the context.
+    NumDevices = 0x1083,
+}
+
+/// Context property specifying the platform to use (`CL_CONTEXT_PLATFORM`).
+pub const CONTEXT_PLATFORM: ContextProperty = 0x1084;
+
+/// cl_command_queue_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum CommandQueueInfo {
+    /// The context specified when the command queue was created.
+    Context = 0x1090,
+    /// The device specified when the command queue was created.
+    Device = 0x1091,
+    /// The command queue reference count.
+    ReferenceCount = 0x1092,
+    /// The properties specified when the command queue was created.
+    Properties = 0x1093,
+    /// The size of the device queue in bytes (OpenCL 2.0).
+    Size = 0x1094,
 }
 
+/// Queue property specifying the command queue properties bit-field (`CL_QUEUE_PROPERTIES`).
+pub const QUEUE_PROPERTIES: QueueProperty = 0x1093;
+
+/// Implements the bitwise operators for a transparent bit-field wrapper.
+macro_rules! bit_field {
+    ($name:ident, $raw:ty) => {
+        impl $name {
+            /// An empty bit-field.
+            pub const NONE: Self = $name(0);
+
+            /// Create the bit-field from a raw value as returned by the OpenCL API.
+            pub fn from_raw(raw: $raw) -> Self {
+                $name(raw)
+            }
+
+            /// Get the raw underlying value.
+            pub fn raw(&self) -> $raw {
+                self.0
+            }
+
+            /// Check whether all bits of `other` are set.
+            pub fn contains(&self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::NONE
+            }
+        }
+
+        impl BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self::Output {
+                Self(self.0 & rhs.0)
+            }
+        }
+    };
+}
+
+/// Properties of a command queue.
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(transparent)]
+pub struct CommandQueueProperties(u64);
+
+impl CommandQueueProperties {
+    /// Commands may be executed out of order.
+    pub const OUT_OF_ORDER_EXEC_MODE_ENABLE: Self = CommandQueueProperties(1 << 0);
+    /// Profiling of commands is enabled.
+    pub const PROFILING_ENABLE: Self = CommandQueueProperties(1 << 1);
+}
+
+bit_field!(CommandQueueProperties, u64);
+
+/// Flags used to create memory objects.
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(transparent)]
+pub struct MemFlags(u64);
+
+impl MemFlags {
+    /// The memory object will be read and written by a kernel. This is the default.
+    pub const READ_WRITE: Self = MemFlags(1 << 0);
+    /// The memory object will be written but not read by a kernel.
+    pub const WRITE_ONLY: Self = MemFlags(1 << 1);
+    /// The memory object is read-only when used inside a kernel.
+    pub const READ_ONLY: Self = MemFlags(1 << 2);
+    /// The implementation uses the memory referenced by the host pointer as the storage bits.
+    pub const USE_HOST_PTR: Self = MemFlags(1 << 3);
+    /// The implementation allocates memory from host accessible memory.
+    pub const ALLOC_HOST_PTR: Self = MemFlags(1 << 4);
+    /// The implementation allocates memory and copies the data referenced by the host pointer.
+    pub const COPY_HOST_PTR: Self = MemFlags(1 << 5);
+    /// The host will only write to the memory object.
+    pub const HOST_WRITE_ONLY: Self = MemFlags(1 << 7);
+    /// The host will only read the memory object.
+    pub const HOST_READ_ONLY: Self = MemFlags(1 << 8);
+    /// The host will not read or write the memory object.
+    pub const HOST_NO_ACCESS: Self = MemFlags(1 << 9);
+    /// The shared virtual memory allocation is fine-grained and can be accessed by the host without mapping.
+    pub const SVM_FINE_GRAIN_BUFFER: Self = MemFlags(1 << 10);
+    /// The shared virtual memory allocation supports atomic operations shared between host and devices.
+    pub const SVM_ATOMICS: Self = MemFlags(1 << 11);
+}
+
+bit_field!(MemFlags, u64);
+
+/// How a memory region is mapped into the host address space.
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(transparent)]
+pub struct MapFlags(u64);
+
+impl MapFlags {
+    /// The mapped region is read by the host.
+    pub const READ: Self = MapFlags(1 << 0);
+    /// The mapped region is written by the host.
+    pub const WRITE: Self = MapFlags(1 << 1);
+    /// The mapped region is completely overwritten by the host, so its contents need not be transferred.
+    pub const WRITE_INVALIDATE_REGION: Self = MapFlags(1 << 2);
+}
+
+bit_field!(MapFlags, u64);
+
+/// Where memory objects are migrated to.
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(transparent)]
+pub struct MigrationFlags(u64);
+
+impl MigrationFlags {
+    /// Migrate to the host instead of the device of the command queue.
+    pub const HOST: Self = MigrationFlags(1 << 0);
+    /// The contents are not needed after the migration, so they need not be transferred.
+    pub const CONTENT_UNDEFINED: Self = MigrationFlags(1 << 1);
+}
+
+bit_field!(MigrationFlags, u64);
+
+/// The shared virtual memory capabilities of a device.
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(transparent)]
+pub struct SvmCapabilities(u64);
+
+impl SvmCapabilities {
+    /// Coarse-grained allocations, which must be mapped for host access.
+    pub const COARSE_GRAIN_BUFFER: Self = SvmCapabilities(1 << 0);
+    /// Fine-grained allocations, which can be accessed by the host without mapping.
+    pub const FINE_GRAIN_BUFFER: Self = SvmCapabilities(1 << 1);
+    /// Any host allocation can be shared with the device.
+    pub const FINE_GRAIN_SYSTEM: Self = SvmCapabilities(1 << 2);
+    /// Atomic operations on fine-grained allocations are shared between host and devices.
+    pub const ATOMICS: Self = SvmCapabilities(1 << 3);
+}
+
+bit_field!(SvmCapabilities, u64);
+
+/// cl_mem_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum MemInfo {
+    /// The type of the memory object.
+    Type = 0x1100,
+    /// The flags specified when the memory object was created.
+    Flags = 0x1101,
+    /// The actual size of the data store associated with the memory object in bytes.
+    Size = 0x1102,
+    /// The host pointer specified when the memory object was created.
+    HostPtr = 0x1103,
+    /// The number of times the memory object is currently mapped.
+    MapCount = 0x1104,
+    /// The memory object reference count.
+    ReferenceCount = 0x1105,
+    /// The context specified when the memory object was created.
+    Context = 0x1106,
+    /// The memory object from which this memory object was created.
+    AssociatedMemObject = 0x1107,
+    /// The offset of a sub-buffer within its parent buffer.
+    Offset = 0x1108,
+}
+
+/// Sub-buffer create type specifying a region of the parent buffer (`CL_BUFFER_CREATE_TYPE_REGION`).
+pub const BUFFER_CREATE_TYPE_REGION: u32 = 0x1220;
+
+/// cl_program_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum ProgramInfo {
+    /// The program reference count.
+    ReferenceCount = 0x1160,
+    /// The context specified when the program was created.
+    Context = 0x1161,
+    /// The number of devices associated with the program.
+    NumDevices = 0x1162,
+    /// The list of devices associated with the program.
+    Devices = 0x1163,
+    /// The program source code.
+    Source = 0x1164,
+    /// The sizes in bytes of the program binaries for each device.
+    BinarySizes = 0x1165,
+    /// The program binaries for each device.
+    Binaries = 0x1166,
+    /// The number of kernels declared in the program.
+    NumKernels = 0x1167,
+    /// A semicolon separated list of kernel names in the program.
+    KernelNames = 0x1168,
+}
+
+/// cl_program_build_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum ProgramBuildInfo {
+    /// The build status of the program for a device.
+    Status = 0x1181,
+    /// The build options specified for the last build of the program for a device.
+    Options = 0x1182,
+    /// The build log of the last build of the program for a device.
+    Log = 0x1183,
+    /// The program binary type for a device.
+    BinaryType = 0x1184,
+}
+
+/// cl_build_status
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(i32)]
+pub enum BuildStatus {
+    /// The last build was successful.
+    Success = 0,
+    /// No build has been performed yet.
+    None = -1,
+    /// The last build generated an error.
+    Error = -2,
+    /// The build has not finished yet.
+    InProgress = -3,
+}
+
+impl From<i32> for BuildStatus {
+    fn from(status: i32) -> Self {
+        match status {
+            0 => BuildStatus::Success,
+            -2 => BuildStatus::Error,
+            -3 => BuildStatus::InProgress,
+            _ => BuildStatus::None,
+        }
+    }
+}
+
+impl Display for BuildStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            BuildStatus::Success => write!(f, "CL_BUILD_SUCCESS"),
+            BuildStatus::None => write!(f, "CL_BUILD_NONE"),
+            BuildStatus::Error => write!(f, "CL_BUILD_ERROR"),
+            BuildStatus::InProgress => write!(f, "CL_BUILD_IN_PROGRESS"),
+        }
+    }
+}
+
+/// cl_kernel_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum KernelInfo {
+    /// The kernel function name.
+    FunctionName = 0x1190,
+    /// The number of arguments of the kernel.
+    NumArgs = 0x1191,
+    /// The kernel reference count.
+    ReferenceCount = 0x1192,
+    /// The context associated with the kernel.
+    Context = 0x1193,
+    /// The program associated with the kernel.
+    Program = 0x1194,
+    /// The attributes specified using `__attribute__` in the kernel declaration.
+    Attributes = 0x1195,
+}
+
+/// cl_kernel_work_group_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum WorkGroupInfo {
+    /// The maximum work-group size the kernel can be executed with on the device.
+    WorkGroupSize = 0x11B0,
+    /// The work-group size specified by `__attribute__((reqd_work_group_size(X, Y, Z)))`, or `(0, 0, 0)`.
+    CompileWorkGroupSize = 0x11B1,
+    /// The amount of local memory used by the kernel, including `__local` kernel arguments set so far.
+    LocalMemSize = 0x11B2,
+    /// The work-group size should be a multiple of this for best performance.
+    PreferredWorkGroupSizeMultiple = 0x11B3,
+    /// The minimum amount of private memory used by each work-item.
+    PrivateMemSize = 0x11B4,
+    /// The maximum global work size of built-in kernels and custom devices.
+    GlobalWorkSize = 0x11B5,
+}
+
+/// cl_kernel_sub_group_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum SubGroupInfo {
+    /// The maximum sub-group size for a given local work size.
+    MaxSubGroupSizeForNdRange = 0x2033,
+    /// The number of sub-groups for a given local work size.
+    SubGroupCountForNdRange = 0x2034,
+    /// The local work size producing a given number of sub-groups.
+    LocalSizeForSubGroupCount = 0x11B8,
+    /// The maximum number of sub-groups in a work-group the kernel can be executed with on the device.
+    MaxNumSubGroups = 0x11B9,
+    /// The number of sub-groups specified by `__attribute__((required_num_sub_groups(N)))`, or `0`.
+    CompileNumSubGroups = 0x11BA,
+}
+
+/// cl_kernel_exec_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum KernelExecInfo {
+    /// Shared virtual memory pointers used indirectly by the kernel.
+    SvmPtrs = 0x11B6,
+    /// Whether the kernel may use fine-grained system allocations.
+    SvmFineGrainSystem = 0x11B7,
+}
+
+/// cl_kernel_arg_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum KernelArgInfo {
+    /// The address qualifier of the argument.
+    AddressQualifier = 0x1196,
+    /// The access qualifier of an image argument.
+    AccessQualifier = 0x1197,
+    /// The type name of the argument.
+    TypeName = 0x1198,
+    /// The type qualifiers of the argument.
+    TypeQualifier = 0x1199,
+    /// The name of the argument.
+    Name = 0x119A,
+}
+
+/// cl_kernel_arg_address_qualifier
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u32)]
+pub enum AddressQualifier {
+    /// `__global`
+    Global = 0x119B,
+    /// `__local`
+    Local = 0x119C,
+    /// `__constant`
+    Constant = 0x119D,
+    /// `__private`, i.e. passed by value.
+    Private = 0x119E,
+}
+
+impl TryFrom<u32> for AddressQualifier {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0x119B => Ok(AddressQualifier::Global),
+            0x119C => Ok(AddressQualifier::Local),
+            0x119D => Ok(AddressQualifier::Constant),
+            0x119E => Ok(AddressQualifier::Private),
+            _ => Err(value),
+        }
+    }
+}
+
+impl Display for AddressQualifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            AddressQualifier::Global => write!(f, "__global"),
+            AddressQualifier::Local => write!(f, "__local"),
+            AddressQualifier::Constant => write!(f, "__constant"),
+            AddressQualifier::Private => write!(f, "__private"),
+        }
+    }
+}
+
+/// cl_kernel_arg_access_qualifier
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u32)]
+pub enum AccessQualifier {
+    /// `__read_only`
+    ReadOnly = 0x11A0,
+    /// `__write_only`
+    WriteOnly = 0x11A1,
+    /// `__read_write`
+    ReadWrite = 0x11A2,
+    /// The argument is not an image or pipe.
+    None = 0x11A3,
+}
+
+impl TryFrom<u32> for AccessQualifier {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0x11A0 => Ok(AccessQualifier::ReadOnly),
+            0x11A1 => Ok(AccessQualifier::WriteOnly),
+            0x11A2 => Ok(AccessQualifier::ReadWrite),
+            0x11A3 => Ok(AccessQualifier::None),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Type qualifiers of a kernel argument.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(transparent)]
+pub struct TypeQualifier(u64);
+
+impl TypeQualifier {
+    /// `const`
+    pub const CONST: Self = TypeQualifier(1 << 0);
+    /// `restrict`
+    pub const RESTRICT: Self = TypeQualifier(1 << 1);
+    /// `volatile`
+    pub const VOLATILE: Self = TypeQualifier(1 << 2);
+    /// The argument is a pipe.
+    pub const PIPE: Self = TypeQualifier(1 << 3);
+}
+
+bit_field!(TypeQualifier, u64);
+
+/// cl_event_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum EventInfo {
+    /// The command queue associated with the event.
+    CommandQueue = 0x11D0,
+    /// The command associated with the event.
+    CommandType = 0x11D1,
+    /// The event reference count.
+    ReferenceCount = 0x11D2,
+    /// The execution status of the command identified by the event.
+    CommandExecutionStatus = 0x11D3,
+    /// The context associated with the event.
+    Context = 0x11D4,
+}
+
+/// cl_profiling_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum ProfilingInfo {
+    /// When the command was enqueued in the command queue by the host.
+    Queued = 0x1280,
+    /// When the command was submitted by the host to the device.
+    Submit = 0x1281,
+    /// When the command started execution on the device.
+    Start = 0x1282,
+    /// When the command finished execution on the device.
+    End = 0x1283,
+    /// When the command and all its child commands finished execution (OpenCL 2.0).
+    Complete = 0x1284,
+}
+
+/// The execution status of a command.
+///
+/// Commands which terminated abnormally report a negative error code instead.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(i32)]
+pub enum ExecutionStatus {
+    /// The command has completed.
+    Complete = 0,
+    /// The device is currently executing the command.
+    Running = 1,
+    /// The command has been submitted to the device.
+    Submitted = 2,
+    /// The command has been enqueued in the command queue.
+    Queued = 3,
+}
+
+impl TryFrom<i32> for ExecutionStatus {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ExecutionStatus::Complete),
+            1 => Ok(ExecutionStatus::Running),
+            2 => Ok(ExecutionStatus::Submitted),
+            3 => Ok(ExecutionStatus::Queued),
+            _ => Err(value),
+        }
+    }
+}
+
+/// cl_mem_object_type
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u32)]
+pub enum MemObjectType {
+    /// A buffer.
+    Buffer = 0x10F0,
+    /// A 2D image.
+    Image2D = 0x10F1,
+    /// A 3D image.
+    Image3D = 0x10F2,
+    /// An array of 2D images.
+    Image2DArray = 0x10F3,
+    /// A 1D image.
+    Image1D = 0x10F4,
+    /// An array of 1D images.
+    Image1DArray = 0x10F5,
+    /// A 1D image created from a buffer.
+    Image1DBuffer = 0x10F6,
+    /// A pipe.
+    Pipe = 0x10F7,
+}
+
+/// cl_image_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum ImageInfo {
+    /// The image format descriptor specified when the image was created.
+    Format = 0x1110,
+    /// The size of each element of the image in bytes.
+    ElementSize = 0x1111,
+    /// The size in bytes of a row of elements.
+    RowPitch = 0x1112,
+    /// The size in bytes of a 2D slice of a 3D image or of each image in an image array.
+    SlicePitch = 0x1113,
+    /// The width of the image in pixels.
+    Width = 0x1114,
+    /// The height of the image in pixels.
+    Height = 0x1115,
+    /// The depth of the image in pixels.
+    Depth = 0x1116,
+    /// The number of images in an image array.
+    ArraySize = 0x1117,
+    /// The buffer a 1D image buffer was created from.
+    Buffer = 0x1118,
+    /// The number of mip levels.
+    NumMipLevels = 0x1119,
+    /// The number of samples.
+    NumSamples = 0x111A,
+}
+
+/// cl_pipe_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum PipeInfo {
+    /// The size of a packet in bytes.
+    PacketSize = 0x1120,
+    /// The maximum number of packets the pipe can hold.
+    MaxPackets = 0x1121,
+}
+
+/// cl_channel_order
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u32)]
+#[allow(missing_docs)]
+pub enum ChannelOrder {
+    R = 0x10B0,
+    A = 0x10B1,
+    RG = 0x10B2,
+    RA = 0x10B3,
+    RGB = 0x10B4,
+    RGBA = 0x10B5,
+    BGRA = 0x10B6,
+    ARGB = 0x10B7,
+    Intensity = 0x10B8,
+    Luminance = 0x10B9,
+    Rx = 0x10BA,
+    RGx = 0x10BB,
+    RGBx = 0x10BC,
+    Depth = 0x10BD,
+    DepthStencil = 0x10BE,
+    SRGB = 0x10BF,
+    SRGBx = 0x10C0,
+    SRGBA = 0x10C1,
+    SBGRA = 0x10C2,
+    ABGR = 0x10C3,
+}
+
+impl ChannelOrder {
+    /// The number of channels stored per pixel, including the padding channel of `Rx`, `RGx`, `RGBx` and `SRGBx`.
+    pub fn channels(&self) -> usize {
+        match *self {
+            ChannelOrder::R | ChannelOrder::A | ChannelOrder::Intensity | ChannelOrder::Luminance | ChannelOrder::Depth => 1,
+            ChannelOrder::RG | ChannelOrder::RA | ChannelOrder::Rx | ChannelOrder::DepthStencil => 2,
+            ChannelOrder::RGB | ChannelOrder::RGx | ChannelOrder::SRGB => 3,
+            ChannelOrder::RGBA | ChannelOrder::RGBx | ChannelOrder::SRGBx | ChannelOrder::BGRA | ChannelOrder::ARGB | ChannelOrder::SRGBA
+            | ChannelOrder::SBGRA | ChannelOrder::ABGR => 4,
+        }
+    }
+}
+
+impl TryFrom<u32> for ChannelOrder {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if (ChannelOrder::R as u32..=ChannelOrder::ABGR as u32).contains(&value) {
+            Ok(unsafe { std::mem::transmute::<u32, ChannelOrder>(value) })
+        } else {
+            Err(value)
+        }
+    }
+}
+
+/// cl_channel_type
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u32)]
+#[allow(missing_docs)]
+pub enum ChannelType {
+    SnormInt8 = 0x10D0,
+    SnormInt16 = 0x10D1,
+    UnormInt8 = 0x10D2,
+    UnormInt16 = 0x10D3,
+    UnormShort565 = 0x10D4,
+    UnormShort555 = 0x10D5,
+    UnormInt101010 = 0x10D6,
+    SignedInt8 = 0x10D7,
+    SignedInt16 = 0x10D8,
+    SignedInt32 = 0x10D9,
+    UnsignedInt8 = 0x10DA,
+    UnsignedInt16 = 0x10DB,
+    UnsignedInt32 = 0x10DC,
+    HalfFloat = 0x10DD,
+    Float = 0x10DE,
+    UnormInt24 = 0x10DF,
+    UnormInt101010_2 = 0x10E0,
+}
+
+impl ChannelType {
+    /// The size of a single channel in bytes, or `None` for packed types storing all channels together.
+    pub fn channel_size(&self) -> Option<usize> {
+        match *self {
+            ChannelType::SnormInt8 | ChannelType::UnormInt8 | ChannelType::SignedInt8 | ChannelType::UnsignedInt8 => Some(1),
+            ChannelType::SnormInt16 | ChannelType::UnormInt16 | ChannelType::SignedInt16 | ChannelType::UnsignedInt16
+            | ChannelType::HalfFloat => Some(2),
+            ChannelType::SignedInt32 | ChannelType::UnsignedInt32 | ChannelType::Float => Some(4),
+            ChannelType::UnormShort565 | ChannelType::UnormShort555 | ChannelType::UnormInt101010
+            | ChannelType::UnormInt24 | ChannelType::UnormInt101010_2 => None,
+        }
+    }
+}
+
+impl TryFrom<u32> for ChannelType {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if (ChannelType::SnormInt8 as u32..=ChannelType::UnormInt101010_2 as u32).contains(&value) {
+            Ok(unsafe { std::mem::transmute::<u32, ChannelType>(value) })
+        } else {
+            Err(value)
+        }
+    }
+}
+
+/// An image format, i.e. the channel order and the channel data type of the pixels.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct ImageFormat {
+    /// The number of channels and their layout.
+    pub channel_order: ChannelOrder,
+    /// The size and type of a channel.
+    pub channel_data_type: ChannelType,
+}
+
+impl ImageFormat {
+    /// Create an image format from channel order and channel data type.
+    pub fn new(channel_order: ChannelOrder, channel_data_type: ChannelType) -> Self {
+        Self { channel_order, channel_data_type }
+    }
+
+    /// The size of a single pixel in bytes.
+    pub fn element_size(&self) -> usize {
+        match self.channel_data_type {
+            ChannelType::UnormShort565 | ChannelType::UnormShort555 => 2,
+            ChannelType::UnormInt101010 | ChannelType::UnormInt101010_2 | ChannelType::UnormInt24 => 4,
+            data_type => self.channel_order.channels() * data_type.channel_size().unwrap_or(0),
+        }
+    }
+}
+
+/// cl_addressing_mode
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u32)]
+pub enum AddressingMode {
+    /// Coordinates outside the image are undefined.
+    None = 0x1130,
+    /// Out of range coordinates are clamped to the edge of the image.
+    ClampToEdge = 0x1131,
+    /// Out of range coordinates return the border color.
+    Clamp = 0x1132,
+    /// Out of range normalized coordinates wrap around.
+    Repeat = 0x1133,
+    /// Out of range normalized coordinates are mirrored.
+    MirroredRepeat = 0x1134,
+}
+
+impl TryFrom<u32> for AddressingMode {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0x1130 => Ok(AddressingMode::None),
+            0x1131 => Ok(AddressingMode::ClampToEdge),
+            0x1132 => Ok(AddressingMode::Clamp),
+            0x1133 => Ok(AddressingMode::Repeat),
+            0x1134 => Ok(AddressingMode::MirroredRepeat),
+            _ => Err(value),
+        }
+    }
+}
+
+/// cl_filter_mode
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u32)]
+pub enum FilterMode {
+    /// The nearest pixel is returned.
+    Nearest = 0x1140,
+    /// The pixels are linearly interpolated.
+    Linear = 0x1141,
+}
+
+impl TryFrom<u32> for FilterMode {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0x1140 => Ok(FilterMode::Nearest),
+            0x1141 => Ok(FilterMode::Linear),
+            _ => Err(value),
+        }
+    }
+}
+
+/// cl_sampler_info
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[repr(u32)]
+pub enum SamplerInfo {
+    /// The sampler reference count.
+    ReferenceCount = 0x1150,
+    /// The context specified when the sampler was created.
+    Context = 0x1151,
+    /// Whether the sampler uses normalized coordinates.
+    NormalizedCoords = 0x1152,
+    /// The addressing mode of the sampler.
+    AddressingMode = 0x1153,
+    /// The filter mode of the sampler.
+    FilterMode = 0x1154,
+}
+
+/// Sampler property specifying whether normalized coordinates are used (`CL_SAMPLER_NORMALIZED_COORDS`).
+pub const SAMPLER_NORMALIZED_COORDS: SamplerProperty = 0x1152;
+
+/// Sampler property specifying the addressing mode (`CL_SAMPLER_ADDRESSING_MODE`).
+pub const SAMPLER_ADDRESSING_MODE: SamplerProperty = 0x1153;
+
+/// Sampler property specifying the filter mode (`CL_SAMPLER_FILTER_MODE`).
+pub const SAMPLER_FILTER_MODE: SamplerProperty = 0x1154;
+
 /// Device type to query/filter for or type of a given device.
 #[derive(Eq, PartialEq, Copy, Clone)]
 #[cfg_attr(test, derive(Debug))]
@@ -213,6 +1097,104 @@ impl Not for DeviceType {
     }
 }
 
+/// Cache or memory levels along which a device can be partitioned.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(transparent)]
+pub struct AffinityDomain(u64);
+
+impl AffinityDomain {
+    /// Compute units sharing a NUMA node.
+    pub const NUMA: Self = AffinityDomain(1 << 0);
+    /// Compute units sharing a level 4 cache.
+    pub const L4_CACHE: Self = AffinityDomain(1 << 1);
+    /// Compute units sharing a level 3 cache.
+    pub const L3_CACHE: Self = AffinityDomain(1 << 2);
+    /// Compute units sharing a level 2 cache.
+    pub const L2_CACHE: Self = AffinityDomain(1 << 3);
+    /// Compute units sharing a level 1 cache.
+    pub const L1_CACHE: Self = AffinityDomain(1 << 4);
+    /// The next partitionable domain, in the order NUMA, L4, L3, L2, L1.
+    pub const NEXT_PARTITIONABLE: Self = AffinityDomain(1 << 5);
+}
+
+bit_field!(AffinityDomain, u64);
+
+/// The ways a device can be partitioned, as reported by `DeviceInfo::PartitionProperties`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(isize)]
+pub enum PartitionKind {
+    /// Partition into sub-devices with the same number of compute units.
+    Equally = 0x1086,
+    /// Partition into sub-devices with the given numbers of compute units.
+    ByCounts = 0x1087,
+    /// Partition along an affinity domain.
+    ByAffinityDomain = 0x1088,
+}
+
+impl TryFrom<PartitionProperty> for PartitionKind {
+    type Error = PartitionProperty;
+
+    fn try_from(value: PartitionProperty) -> Result<Self, Self::Error> {
+        match value {
+            0x1086 => Ok(PartitionKind::Equally),
+            0x1087 => Ok(PartitionKind::ByCounts),
+            0x1088 => Ok(PartitionKind::ByAffinityDomain),
+            _ => Err(value),
+        }
+    }
+}
+
+/// How a device is partitioned into sub-devices.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum PartitionScheme {
+    /// As many sub-devices as possible with the given number of compute units each.
+    Equally(u32),
+    /// One sub-device for each of the given numbers of compute units.
+    ByCounts(Vec<u32>),
+    /// One sub-device for each instance of the given affinity domain.
+    ByAffinityDomain(AffinityDomain),
+}
+
+impl PartitionScheme {
+    /// The kind of the partition scheme.
+    pub fn kind(&self) -> PartitionKind {
+        match self {
+            PartitionScheme::Equally(_) => PartitionKind::Equally,
+            PartitionScheme::ByCounts(_) => PartitionKind::ByCounts,
+            PartitionScheme::ByAffinityDomain(_) => PartitionKind::ByAffinityDomain,
+        }
+    }
+
+    /// Encode the scheme as zero terminated property list for `clCreateSubDevices`.
+    pub fn to_properties(&self) -> Vec<PartitionProperty> {
+        let mut properties = vec![self.kind() as PartitionProperty];
+        match self {
+            PartitionScheme::Equally(units) => properties.push(*units as PartitionProperty),
+            PartitionScheme::ByCounts(counts) => {
+                properties.extend(counts.iter().map(|&count| count as PartitionProperty));
+                properties.push(0);
+            }
+            PartitionScheme::ByAffinityDomain(domain) => properties.push(domain.raw() as PartitionProperty),
+        }
+        properties.push(0);
+        properties
+    }
+
+    /// Decode a property list as returned by `DeviceInfo::PartitionType`.
+    ///
+    /// Returns `None` for an empty list, which is reported for devices that are not sub-devices.
+    pub fn from_properties(properties: &[PartitionProperty]) -> Option<Self> {
+        let (&kind, rest) = properties.split_first()?;
+        match PartitionKind::try_from(kind).ok()? {
+            PartitionKind::Equally => Some(PartitionScheme::Equally(*rest.first()? as u32)),
+            PartitionKind::ByCounts => Some(PartitionScheme::ByCounts(
+                rest.iter().take_while(|&&count| count != 0).map(|&count| count as u32).collect(),
+            )),
+            PartitionKind::ByAffinityDomain => Some(PartitionScheme::ByAffinityDomain(AffinityDomain::from_raw(*rest.first()? as u64))),
+        }
+    }
+}
+
 /// An OpenCL profile (Full or embedded).
 #[derive(Eq, PartialEq, Copy, Clone)]
 #[cfg_attr(test, derive(Debug))]
@@ -287,8 +1269,8 @@ impl TryFrom<String> for Version {
         }
 
         let (major_str, minor_str) = parts[1].split_once('.').ok_or_else(|| value.clone())?;
-        let major = u8::from_str(major_str).or_else(|_| Err(value.clone()))?;
-        let minor = u8::from_str(minor_str).or_else(|_| Err(value.clone()))?;
+        let major = u8::from_str(major_str).map_err(|_| value.clone())?;
+        let minor = u8::from_str(minor_str).map_err(|_| value.clone())?;
 
         let extra = if parts.len() == 3 {
             Some(parts[2].to_string())